@@ -0,0 +1,289 @@
+use std::future::Future;
+
+use futures_util::{stream, Stream, StreamExt};
+
+use crate::{Raxios, RaxiosResult};
+
+/// Controls how `Raxios::batch` and `Raxios::batch_stream` run their requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// The maximum number of requests in flight at once. Defaults to `10`, and `0` is treated as `1`
+    pub concurrency: usize,
+    /// Stop at the first error, dropping (and so cancelling) every request still in flight and never
+    /// starting the rest. Defaults to `false`
+    pub fail_fast: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 10,
+            fail_fast: false,
+        }
+    }
+}
+
+impl Raxios {
+    /// Runs `requests` with at most `options.concurrency` in flight at once, and yields every
+    /// result alongside the index of its request as soon as it completes.
+    ///
+    /// Each request is a closure which is handed a clone of this instance to send its call with,
+    /// and is only called as earlier requests complete, so `requests` may be a lazy iterator over
+    /// any number of calls. When `options.fail_fast` is set, the stream ends right after
+    /// the first error.
+    ///
+    /// * `requests` - The requests to run, such as `endpoints.map(|endpoint| move |raxios: Raxios| async move { raxios.get::<T>(&endpoint, None).await })`
+    /// * `options` - The concurrency limit and whether to stop at the first error
+    pub fn batch_stream<T, I, R, F>(
+        &self,
+        requests: I,
+        options: Option<BatchOptions>,
+    ) -> impl Stream<Item = (usize, RaxiosResult<T>)>
+    where
+        I: IntoIterator<Item = R>,
+        R: FnOnce(Raxios) -> F,
+        F: Future<Output = RaxiosResult<T>>,
+    {
+        let options = options.unwrap_or_default();
+        let raxios = self.clone();
+        let requests = requests
+            .into_iter()
+            .enumerate()
+            .map(move |(index, request)| {
+                let request = request(raxios.clone());
+                async move { (index, request.await) }
+            });
+
+        let completed =
+            Box::pin(stream::iter(requests).buffer_unordered(options.concurrency.max(1)));
+
+        // Ends before polling `completed` again once an error has been yielded, so no more
+        // requests are started
+        stream::unfold(
+            (completed, false),
+            move |(mut completed, failed)| async move {
+                if failed {
+                    return None;
+                }
+                let (index, result) = completed.next().await?;
+                let failed = options.fail_fast && result.is_err();
+                Some(((index, result), (completed, failed)))
+            },
+        )
+    }
+
+    /// The `axios.all` of raxios: runs `requests` with at most `options.concurrency` in flight at
+    /// once, and returns their results in the order of `requests`.
+    ///
+    /// When `options.fail_fast` is set, the first error is returned as soon as it happens, and
+    /// every other request is cancelled or never started. See `Raxios::batch_stream` for how
+    /// requests are given.
+    ///
+    /// * `requests` - The requests to run, such as `endpoints.map(|endpoint| move |raxios: Raxios| async move { raxios.get::<T>(&endpoint, None).await })`
+    /// * `options` - The concurrency limit and whether to stop at the first error
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use raxios::{BatchOptions, Raxios};
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct User {
+    ///     id: u32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     for id in 1..=3 {
+    ///         server.mock(|when, then| {
+    ///             when.path(format!("/users/{id}")).method(GET);
+    ///             then.status(200).json_body(serde_json::json!({ "id": id }));
+    ///         });
+    ///     }
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let endpoints = (1..=3).map(|id| format!("/users/{id}"));
+    ///     let requests = endpoints.map(|endpoint| {
+    ///         move |raxios: Raxios| async move { raxios.get::<User>(&endpoint, None).await }
+    ///     });
+    ///
+    ///     let options = BatchOptions {
+    ///         concurrency: 2,
+    ///         ..Default::default()
+    ///     };
+    ///     let users = raxios
+    ///         .batch(requests, Some(options))
+    ///         .await
+    ///         .unwrap()
+    ///         .into_iter()
+    ///         .map(|response| response.unwrap().body.unwrap().id)
+    ///         .collect::<Vec<_>>();
+    ///
+    ///     assert_eq!(vec![1, 2, 3], users);
+    /// }
+    /// ```
+    pub async fn batch<T, I, R, F>(
+        &self,
+        requests: I,
+        options: Option<BatchOptions>,
+    ) -> RaxiosResult<Vec<RaxiosResult<T>>>
+    where
+        I: IntoIterator<Item = R>,
+        R: FnOnce(Raxios) -> F,
+        F: Future<Output = RaxiosResult<T>>,
+    {
+        let fail_fast = options.unwrap_or_default().fail_fast;
+        let mut results: Vec<Option<RaxiosResult<T>>> = Vec::new();
+
+        let completed = self.batch_stream(requests, options);
+        futures_util::pin_mut!(completed);
+        while let Some((index, result)) = completed.next().await {
+            let result = match result {
+                Err(err) if fail_fast => return Err(err),
+                result => result,
+            };
+            if results.len() <= index {
+                results.resize_with(index + 1, || None);
+            }
+            results[index] = Some(result);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures_util::StreamExt;
+
+    use super::BatchOptions;
+    use crate::{Raxios, RaxiosError, RaxiosResult};
+
+    fn raxios() -> Raxios {
+        Raxios::new("http://localhost", None).unwrap()
+    }
+
+    /// A request which completes after `delay_ms`, failing when `id` is 0, and records how many
+    /// requests were in flight at once
+    async fn request(
+        id: u64,
+        delay_ms: u64,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    ) -> RaxiosResult<u64> {
+        let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        match id {
+            0 => Err(RaxiosError::InvalidUrl(String::from("failed"))),
+            id => Ok(id),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_caps_concurrency_and_keeps_order() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let requests = (1..=8).map(|id| {
+            let request = request(id, (9 - id) * 5, in_flight.clone(), max_in_flight.clone());
+            move |_: Raxios| request
+        });
+
+        let options = BatchOptions {
+            concurrency: 3,
+            ..Default::default()
+        };
+        let results = raxios()
+            .batch(requests, Some(options))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+
+        assert_eq!((1..=8).collect::<Vec<_>>(), results);
+        assert_eq!(3, max_in_flight.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_batch_fail_fast() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let started = Arc::new(AtomicUsize::new(0));
+        let requests = [(1, 50), (0, 1), (2, 50), (3, 50)].map(|(id, delay_ms)| {
+            let started = started.clone();
+            let request = request(id, delay_ms, in_flight.clone(), max_in_flight.clone());
+            move |_: Raxios| async move {
+                started.fetch_add(1, Ordering::SeqCst);
+                request.await
+            }
+        });
+
+        let options = BatchOptions {
+            concurrency: 2,
+            fail_fast: true,
+        };
+        assert!(raxios().batch(requests, Some(options)).await.is_err());
+        // The last two requests are never started
+        assert_eq!(2, started.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_batch_stream_fail_fast_ends_without_starting_more() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let started = Arc::new(AtomicUsize::new(0));
+        let requests = [(1, 50), (0, 1), (2, 50), (3, 50)].map(|(id, delay_ms)| {
+            let started = started.clone();
+            let request = request(id, delay_ms, in_flight.clone(), max_in_flight.clone());
+            move |_: Raxios| async move {
+                started.fetch_add(1, Ordering::SeqCst);
+                request.await
+            }
+        });
+
+        let options = BatchOptions {
+            concurrency: 2,
+            fail_fast: true,
+        };
+        let raxios = raxios();
+        let completed = raxios.batch_stream(requests, Some(options));
+        futures_util::pin_mut!(completed);
+
+        let (index, result) = completed.next().await.unwrap();
+        assert_eq!(1, index);
+        assert!(result.is_err());
+        assert!(completed.next().await.is_none());
+        assert_eq!(2, started.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_batch_stream_yields_in_completion_order() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let requests = [(1, 30), (0, 10), (3, 20)].map(|(id, delay_ms)| {
+            let request = request(id, delay_ms, in_flight.clone(), max_in_flight.clone());
+            move |_: Raxios| request
+        });
+
+        let completed = raxios()
+            .batch_stream(requests, None)
+            .map(|(index, result)| (index, result.ok()))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(vec![(1, None), (2, Some(3)), (0, Some(1))], completed);
+    }
+}
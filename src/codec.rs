@@ -0,0 +1,358 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{DeserializationError, MediaType, SerializationError};
+
+/// The value a `Codec` decodes a body into. Codecs hand a `serde::Deserializer` for the body to
+/// `DecodeTarget::deserialize`, which deserializes the type requested by the caller from it.
+pub struct DecodeTarget<'a> {
+    sink: &'a mut dyn for<'de> FnMut(
+        &mut dyn erased_serde::Deserializer<'de>,
+    ) -> Result<(), erased_serde::Error>,
+}
+
+impl<'a> DecodeTarget<'a> {
+    pub(crate) fn new(
+        sink: &'a mut dyn for<'de> FnMut(
+            &mut dyn erased_serde::Deserializer<'de>,
+        ) -> Result<(), erased_serde::Error>,
+    ) -> Self {
+        Self { sink }
+    }
+
+    /// Deserializes the requested type from `deserializer`
+    pub fn deserialize<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut erased = <dyn erased_serde::Deserializer<'de>>::erase(deserializer);
+        (self.sink)(&mut erased).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Encodes request bodies and decodes response bodies for one or more media types.
+///
+/// Implement this trait and register it on `RaxiosConfig::codecs` to support a content type
+/// which raxios does not know about.
+///
+/// # Example
+/// ```rust
+/// use raxios::{erased_serde, Codec, DecodeTarget, DeserializationError, SerializationError};
+///
+/// /// Sends and receives `application/vnd.example+json` with pretty printed bodies
+/// struct PrettyJson;
+///
+/// impl Codec for PrettyJson {
+///     fn media_types(&self) -> &[&str] {
+///         &["application/vnd.example+json"]
+///     }
+///
+///     fn encode(
+///         &self,
+///         value: &dyn erased_serde::Serialize,
+///     ) -> Result<Vec<u8>, SerializationError> {
+///         serde_json::to_vec_pretty(value).map_err(SerializationError::Json)
+///     }
+///
+///     fn decode(
+///         &self,
+///         body: &[u8],
+///         _charset: Option<&str>,
+///         target: &mut DecodeTarget,
+///     ) -> Result<(), DeserializationError> {
+///         target
+///             .deserialize(&mut serde_json::Deserializer::from_slice(body))
+///             .map_err(DeserializationError::Json)
+///     }
+/// }
+/// ```
+pub trait Codec: Send + Sync {
+    /// The media types (such as `application/json`) which this codec reads and writes
+    fn media_types(&self) -> &[&str];
+
+    /// Whether this codec can read and write `media_type`. Defaults to comparing the essence of
+    /// `media_type` against `media_types`, ignoring case and parameters.
+    fn handles(&self, media_type: &MediaType) -> bool {
+        let essence = media_type.essence();
+        self.media_types()
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(&essence))
+    }
+
+    /// Encodes `value` into a request body
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, SerializationError>;
+
+    /// Decodes a response body into `target`. `charset` is the charset parameter of the response
+    /// content-type, if any.
+    fn decode(
+        &self,
+        body: &[u8],
+        charset: Option<&str>,
+        target: &mut DecodeTarget,
+    ) -> Result<(), DeserializationError>;
+}
+
+/// `application/json`, and any media type with a `+json` suffix
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn media_types(&self) -> &[&str] {
+        &["application/json"]
+    }
+
+    fn handles(&self, media_type: &MediaType) -> bool {
+        media_type.essence() == "application/json" || media_type.suffix() == Some("json")
+    }
+
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, SerializationError> {
+        serde_json::to_vec(value).map_err(SerializationError::Json)
+    }
+
+    fn decode(
+        &self,
+        body: &[u8],
+        _charset: Option<&str>,
+        target: &mut DecodeTarget,
+    ) -> Result<(), DeserializationError> {
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        target
+            .deserialize(&mut deserializer)
+            .and_then(|_| deserializer.end())
+            .map_err(DeserializationError::Json)
+    }
+}
+
+/// `text/xml`, `application/xml`, and any media type with a `+xml` suffix
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XmlCodec;
+
+impl Codec for XmlCodec {
+    fn media_types(&self) -> &[&str] {
+        &["text/xml", "application/xml"]
+    }
+
+    fn handles(&self, media_type: &MediaType) -> bool {
+        matches!(
+            media_type.essence().as_str(),
+            "text/xml" | "application/xml"
+        ) || media_type.suffix() == Some("xml")
+    }
+
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, SerializationError> {
+        serde_xml_rs::to_string(&value)
+            .map(String::into_bytes)
+            .map_err(SerializationError::Xml)
+    }
+
+    fn decode(
+        &self,
+        body: &[u8],
+        _charset: Option<&str>,
+        target: &mut DecodeTarget,
+    ) -> Result<(), DeserializationError> {
+        let body = std::str::from_utf8(body).map_err(|_| {
+            DeserializationError::Unknown(String::from("Response body does not contain valid Utf8"))
+        })?;
+        target
+            .deserialize(&mut serde_xml_rs::Deserializer::new_from_reader(
+                body.as_bytes(),
+            ))
+            .map_err(DeserializationError::Xml)
+    }
+}
+
+/// `application/x-www-form-urlencoded`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UrlEncodedCodec;
+
+impl Codec for UrlEncodedCodec {
+    fn media_types(&self) -> &[&str] {
+        &["application/x-www-form-urlencoded"]
+    }
+
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, SerializationError> {
+        serde_urlencoded::to_string(value)
+            .map(String::into_bytes)
+            .map_err(SerializationError::UrlEncoded)
+    }
+
+    fn decode(
+        &self,
+        body: &[u8],
+        _charset: Option<&str>,
+        target: &mut DecodeTarget,
+    ) -> Result<(), DeserializationError> {
+        target
+            .deserialize(serde_urlencoded::Deserializer::new(form_urlencoded::parse(
+                body,
+            )))
+            .map_err(DeserializationError::UrlEncoded)
+    }
+}
+
+/// The codecs a `Raxios` instance uses to encode request bodies and decode response bodies.
+///
+/// The default registry contains `JsonCodec`, `XmlCodec` and `UrlEncodedCodec`. Codecs which are
+/// registered later take precedence over those registered earlier, so a built-in codec can be
+/// replaced by registering another codec for the same media type.
+#[derive(Clone)]
+pub struct CodecRegistry {
+    codecs: Vec<Arc<dyn Codec>>,
+}
+
+impl Default for CodecRegistry {
+    fn default() -> Self {
+        Self {
+            codecs: vec![
+                Arc::new(JsonCodec),
+                Arc::new(XmlCodec),
+                Arc::new(UrlEncodedCodec),
+            ],
+        }
+    }
+}
+
+impl Debug for CodecRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.codecs.iter().map(|codec| codec.media_types()))
+            .finish()
+    }
+}
+
+impl CodecRegistry {
+    /// Registers `codec`, taking precedence over any codec already registered for the same media types
+    ///
+    /// # Example
+    /// ```rust
+    /// use raxios::{CodecRegistry, JsonCodec, MediaType};
+    ///
+    /// let mut codecs = CodecRegistry::default();
+    /// codecs.register(JsonCodec);
+    ///
+    /// let media_type: MediaType = "application/problem+json".parse().unwrap();
+    /// assert!(codecs.find(&media_type).is_some());
+    /// ```
+    pub fn register<C>(&mut self, codec: C) -> &mut Self
+    where
+        C: Codec + 'static,
+    {
+        self.codecs.push(Arc::new(codec));
+        self
+    }
+
+    /// Finds the most recently registered codec which handles `media_type`
+    pub fn find(&self, media_type: &MediaType) -> Option<&dyn Codec> {
+        self.codecs
+            .iter()
+            .rev()
+            .find(|codec| codec.handles(media_type))
+            .map(|codec| codec.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Codec, CodecRegistry, DecodeTarget, JsonCodec, UrlEncodedCodec, XmlCodec};
+    use crate::{DeserializationError, MediaType, SerializationError};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Item {
+        name: String,
+    }
+
+    fn decode<T>(codec: &dyn Codec, body: &[u8]) -> Result<T, DeserializationError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let mut value = None;
+        let mut sink = |deserializer: &mut dyn erased_serde::Deserializer<'_>| {
+            value = Some(erased_serde::deserialize::<T>(deserializer)?);
+            Ok(())
+        };
+        codec.decode(body, None, &mut DecodeTarget::new(&mut sink))?;
+        Ok(value.unwrap())
+    }
+
+    struct Upper;
+
+    impl Codec for Upper {
+        fn media_types(&self) -> &[&str] {
+            &["application/json"]
+        }
+
+        fn encode(
+            &self,
+            _value: &dyn erased_serde::Serialize,
+        ) -> Result<Vec<u8>, SerializationError> {
+            Ok(b"UPPER".to_vec())
+        }
+
+        fn decode(
+            &self,
+            _body: &[u8],
+            _charset: Option<&str>,
+            _target: &mut DecodeTarget,
+        ) -> Result<(), DeserializationError> {
+            Ok(())
+        }
+    }
+
+    fn media_type(string: &str) -> MediaType {
+        string.parse().unwrap()
+    }
+
+    #[test]
+    fn test_builtin_codecs_round_trip() {
+        let item = Item {
+            name: String::from("raxios"),
+        };
+
+        for codec in [
+            &JsonCodec as &dyn Codec,
+            &XmlCodec as &dyn Codec,
+            &UrlEncodedCodec as &dyn Codec,
+        ] {
+            let body = codec.encode(&item).unwrap();
+            assert_eq!(item, decode::<Item>(codec, &body).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_json_codec_rejects_trailing_characters() {
+        assert!(matches!(
+            decode::<Item>(&JsonCodec, b"{\"name\":\"raxios\"} trailing"),
+            Err(DeserializationError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_registry_finds_builtin_codecs() {
+        let codecs = CodecRegistry::default();
+
+        for string in [
+            "application/json; charset=utf-8",
+            "application/hal+json",
+            "Text/XML",
+            "application/atom+xml",
+            "application/x-www-form-urlencoded",
+        ] {
+            assert!(codecs.find(&media_type(string)).is_some(), "{string}");
+        }
+        assert!(codecs.find(&media_type("text/html")).is_none());
+    }
+
+    #[test]
+    fn test_registered_codecs_take_precedence() {
+        let mut codecs = CodecRegistry::default();
+        codecs.register(Upper);
+
+        let codec = codecs.find(&media_type("application/json")).unwrap();
+        assert_eq!(b"UPPER".to_vec(), codec.encode(&()).unwrap());
+
+        let codec = codecs.find(&media_type("application/ld+json")).unwrap();
+        assert_eq!(b"null".to_vec(), codec.encode(&()).unwrap());
+    }
+}
@@ -0,0 +1,782 @@
+use bytes::{Bytes, BytesMut};
+use csv::{DeserializeError, StringRecord};
+use futures_util::{stream, Stream, StreamExt};
+use serde::{
+    de::{self, value::StrDeserializer, DeserializeOwned, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, ser, Deserialize, Serialize,
+};
+
+use std::str::FromStr;
+
+use reqwest::{header::CONTENT_TYPE, Response};
+
+use crate::{
+    raw, ContentType, CsvOptions, DeserializationError, MediaType, Raxios, RaxiosError,
+    RaxiosOptions, RaxiosResult, RaxiosStream, SerializationError,
+};
+
+fn csv_error(err: csv::Error) -> RaxiosError {
+    RaxiosError::DeserializationError(DeserializationError::Csv(err))
+}
+
+fn row_error(err: DeserializeError) -> RaxiosError {
+    RaxiosError::DeserializationError(DeserializationError::CsvRow(err))
+}
+
+/// Serializes a sequence of rows as CSV. Each element of `data` becomes one row.
+pub(crate) fn to_csv<U: Serialize>(data: &U, options: &CsvOptions) -> RaxiosResult<Vec<u8>> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.has_headers)
+        .from_writer(Vec::new());
+
+    data.serialize(RowsSerializer {
+        writer: &mut writer,
+    })
+    .and_then(|_| writer.flush().map_err(csv::Error::from))
+    .map_err(|e| RaxiosError::SerializationError(SerializationError::Csv(e)))?;
+
+    writer.into_inner().map_err(|e| {
+        RaxiosError::SerializationError(SerializationError::Csv(csv::Error::from(e.into_error())))
+    })
+}
+
+/// Deserializes a fully buffered CSV body as if it were a sequence of its rows
+pub(crate) fn from_csv<T>(
+    raw_body: &[u8],
+    charset: Option<&str>,
+    options: &CsvOptions,
+) -> RaxiosResult<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let body = raw::decode_text(raw_body, charset);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.has_headers)
+        .from_reader(body.as_bytes());
+
+    let headers = if options.has_headers {
+        Some(reader.headers().map_err(csv_error)?.clone())
+    } else {
+        None
+    };
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(csv_error)?;
+
+    T::deserialize(RowsDeserializer {
+        records: &records,
+        headers: headers.as_ref(),
+    })
+    .map_err(row_error)
+}
+
+/// Splits a streamed CSV body into records as bytes arrive. Newlines inside of quoted fields do
+/// not end a record.
+fn split_csv_records<S>(body: S) -> impl Stream<Item = RaxiosResult<Bytes>> + Send
+where
+    S: Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+{
+    // (body, buffer, scanned up to, inside of quotes, body finished)
+    let state = (Box::pin(body), BytesMut::new(), 0, false, false);
+
+    stream::unfold(
+        state,
+        |(mut body, mut buffer, mut position, mut in_quotes, mut done)| async move {
+            loop {
+                while position < buffer.len() {
+                    match buffer[position] {
+                        b'"' => in_quotes = !in_quotes,
+                        b'\n' if !in_quotes => {
+                            let record = buffer.split_to(position + 1).freeze();
+                            position = 0;
+                            if record.iter().all(u8::is_ascii_whitespace) {
+                                continue;
+                            }
+                            return Some((Ok(record), (body, buffer, position, in_quotes, done)));
+                        }
+                        _ => {}
+                    }
+                    position += 1;
+                }
+
+                if done {
+                    let record = buffer.split().freeze();
+                    if record.iter().all(u8::is_ascii_whitespace) {
+                        return None;
+                    }
+                    return Some((Ok(record), (body, buffer, 0, false, done)));
+                }
+
+                match body.next().await {
+                    Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                    Some(Err(err)) => {
+                        return Some((
+                            Err(RaxiosError::UnableToSendRequest { err }),
+                            (body, BytesMut::new(), 0, false, true),
+                        ))
+                    }
+                    None => done = true,
+                }
+            }
+        },
+    )
+}
+
+/// The charset of a streamed CSV response. Records are split on ASCII newlines and quotes before
+/// they are decoded, so charsets which are not ASCII-compatible, such as UTF-16, are rejected.
+fn streamed_charset(response: &Response) -> RaxiosResult<Option<String>> {
+    let charset = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| MediaType::from_str(value).ok())
+        .and_then(|media_type| media_type.charset().map(str::to_owned));
+
+    match charset
+        .as_deref()
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+    {
+        Some(encoding) if !encoding.is_ascii_compatible() => Err(
+            RaxiosError::DeserializationError(DeserializationError::Unknown(format!(
+                "Unable to stream a CSV body with charset {}",
+                encoding.name()
+            ))),
+        ),
+        _ => Ok(charset),
+    }
+}
+
+fn parse_record(record: &[u8], options: &CsvOptions) -> RaxiosResult<Option<StringRecord>> {
+    csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(false)
+        .from_reader(record)
+        .records()
+        .next()
+        .transpose()
+        .map_err(csv_error)
+}
+
+impl Raxios {
+    pub(crate) fn csv_options(&self, options: Option<&RaxiosOptions>) -> CsvOptions {
+        options
+            .and_then(|options| options.csv.clone())
+            .unwrap_or_else(|| self.defaults().config.csv.clone())
+    }
+
+    /// Sends an HTTP GET request and streams the `text/csv` response one row at a time as bytes arrive.
+    /// The delimiter and header handling are taken from `RaxiosOptions::csv` or `RaxiosConfig::csv`.
+    ///
+    /// A row which fails to deserialize yields an `Err` without ending the stream. Rows are decoded
+    /// with the `charset` of the response like a buffered `text/csv` body, and a response with a
+    /// charset which is not ASCII-compatible, such as UTF-16, is rejected.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures_util::StreamExt;
+    /// use httpmock::prelude::*;
+    /// use raxios::Raxios;
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct Row {
+    ///     name: String,
+    ///     total: u32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/report").method(GET);
+    ///         then.status(200)
+    ///             .header("content-type", "text/csv")
+    ///             .body("name,total\nwidgets,4\ngadgets,2\n");
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let rows: Vec<Row> = raxios
+    ///         .get_csv::<Row>("/report", None)
+    ///         .await
+    ///         .unwrap()
+    ///         .map(Result::unwrap)
+    ///         .collect()
+    ///         .await;
+    ///
+    ///     assert_eq!(Row { name: String::from("gadgets"), total: 2 }, rows[1]);
+    /// }
+    /// ```
+    pub async fn get_csv<T>(
+        &self,
+        endpoint: &str,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosStream<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut options = options.unwrap_or_default();
+        options.accept.get_or_insert(ContentType::Csv);
+        let csv_options = self.csv_options(Some(&options));
+
+        let response = self
+            .build_raw_request::<Vec<u8>>(
+                None,
+                Some(&options),
+                self.client.get(self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+        let response = self.check_response_and_return_err(response).await?;
+        let charset = streamed_charset(&response)?;
+
+        let mut headers: Option<StringRecord> = None;
+        let rows = split_csv_records(response.bytes_stream()).filter_map(move |record| {
+            let row = match record.and_then(|record| {
+                let record = raw::decode_text(&record, charset.as_deref());
+                parse_record(record.as_bytes(), &csv_options)
+            }) {
+                Ok(Some(row)) => row,
+                Ok(None) => return futures_util::future::ready(None),
+                Err(err) => return futures_util::future::ready(Some(Err(err))),
+            };
+            if csv_options.has_headers && headers.is_none() {
+                headers = Some(row);
+                return futures_util::future::ready(None);
+            }
+            futures_util::future::ready(Some(
+                T::deserialize(RecordDeserializer {
+                    record: &row,
+                    headers: headers.as_ref(),
+                })
+                .map_err(row_error),
+            ))
+        });
+
+        Ok(Box::pin(rows))
+    }
+}
+
+/// Writes each element of a sequence as one CSV row
+struct RowsSerializer<'a> {
+    writer: &'a mut csv::Writer<Vec<u8>>,
+}
+
+fn not_rows<T>() -> Result<T, csv::Error> {
+    Err(ser::Error::custom(
+        "A CSV body must be a sequence of rows, such as a Vec",
+    ))
+}
+
+impl<'a> ser::Serializer for RowsSerializer<'a> {
+    type Ok = ();
+    type Error = csv::Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = ser::Impossible<(), csv::Error>;
+    type SerializeTupleVariant = ser::Impossible<(), csv::Error>;
+    type SerializeMap = ser::Impossible<(), csv::Error>;
+    type SerializeStruct = ser::Impossible<(), csv::Error>;
+    type SerializeStructVariant = ser::Impossible<(), csv::Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_none(self) -> Result<(), csv::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), csv::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), csv::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), csv::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), csv::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, csv::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, csv::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, csv::Error> {
+        not_rows()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, csv::Error> {
+        not_rows()
+    }
+}
+
+impl<'a> ser::SerializeSeq for RowsSerializer<'a> {
+    type Ok = ();
+    type Error = csv::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), csv::Error> {
+        self.writer.serialize(value)
+    }
+
+    fn end(self) -> Result<(), csv::Error> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTuple for RowsSerializer<'a> {
+    type Ok = ();
+    type Error = csv::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), csv::Error> {
+        self.writer.serialize(value)
+    }
+
+    fn end(self) -> Result<(), csv::Error> {
+        Ok(())
+    }
+}
+
+/// Presents a set of rows as a sequence
+struct RowsDeserializer<'a> {
+    records: &'a [StringRecord],
+    headers: Option<&'a StringRecord>,
+}
+
+impl<'de, 'a> de::Deserializer<'de> for RowsDeserializer<'a> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        let headers = self.headers;
+        visitor.visit_seq(de::value::SeqDeserializer::new(
+            self.records
+                .iter()
+                .map(move |record| RecordDeserializer { record, headers }),
+        ))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit_struct seq tuple
+        tuple_struct map struct enum identifier
+    }
+}
+
+/// Presents a single row as a map of header to field, or a sequence of fields if there are no headers
+struct RecordDeserializer<'a> {
+    record: &'a StringRecord,
+    headers: Option<&'a StringRecord>,
+}
+
+impl<'de, 'a> IntoDeserializer<'de, DeserializeError> for RecordDeserializer<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for RecordDeserializer<'a> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match self.headers {
+            Some(headers) => visitor.visit_map(de::value::MapDeserializer::new(
+                headers
+                    .iter()
+                    .zip(self.record.iter().map(FieldDeserializer)),
+            )),
+            None => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        visitor.visit_seq(de::value::SeqDeserializer::new(
+            self.record.iter().map(FieldDeserializer),
+        ))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct map struct enum identifier
+    }
+}
+
+/// Deserializes a single CSV field, parsing numbers and booleans on demand
+struct FieldDeserializer<'a>(&'a str);
+
+impl<'de, 'a> IntoDeserializer<'de, DeserializeError> for FieldDeserializer<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+                match self.0.trim().parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(err) => Err(de::Error::custom(format!("{err}: {:?}", self.0))),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for FieldDeserializer<'a> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        visitor.visit_string(self.0.to_owned())
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        StrDeserializer::<DeserializeError>::new(self.0).deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::{stream, StreamExt};
+    use httpmock::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use super::{from_csv, split_csv_records, to_csv};
+    use crate::{CsvOptions, Raxios};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        name: String,
+        total: u32,
+        note: Option<String>,
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                name: String::from("widgets"),
+                total: 4,
+                note: None,
+            },
+            Row {
+                name: String::from("gadgets, large"),
+                total: 2,
+                note: Some(String::from("line one\nline two")),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let body = to_csv(&rows(), &CsvOptions::default()).unwrap();
+        assert_eq!(
+            "name,total,note\nwidgets,4,\n\"gadgets, large\",2,\"line one\nline two\"\n",
+            String::from_utf8(body.clone()).unwrap()
+        );
+        assert_eq!(
+            rows(),
+            from_csv::<Vec<Row>>(&body, None, &CsvOptions::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_csv_without_headers_and_custom_delimiter() {
+        let options = CsvOptions {
+            delimiter: b';',
+            has_headers: false,
+        };
+        let body = to_csv(&rows(), &options).unwrap();
+        assert!(body.starts_with(b"widgets;4;\n"));
+
+        let parsed: Vec<(String, u32, Option<String>)> = from_csv(&body, None, &options).unwrap();
+        assert_eq!(String::from("gadgets, large"), parsed[1].0);
+        assert_eq!(None, parsed[0].2);
+    }
+
+    #[test]
+    fn test_csv_body_must_be_a_sequence() {
+        assert!(to_csv(&rows().remove(0), &CsvOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_csv_invalid_field() {
+        let result = from_csv::<Vec<Row>>(b"name,total,note\na,b,\n", None, &CsvOptions::default());
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_split_csv_records_with_quoted_newlines() {
+        let chunks = vec![
+            Ok(Bytes::from_static(b"a,b\n1,\"x\n")),
+            Ok(Bytes::from_static(b"y\"\n\n2,z")),
+        ];
+        let records: Vec<Bytes> = split_csv_records(stream::iter(chunks))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(
+            vec![
+                Bytes::from_static(b"a,b\n"),
+                Bytes::from_static(b"1,\"x\ny\"\n"),
+                Bytes::from_static(b"2,z"),
+            ],
+            records
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_csv_decodes_charset() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/latin1").method(GET);
+            then.status(200)
+                .header("content-type", "text/csv; charset=iso-8859-1")
+                .body(b"name,total,note\ncaf\xe9,1,\n".as_slice());
+        });
+        server.mock(|when, then| {
+            when.path("/utf16").method(GET);
+            then.status(200)
+                .header("content-type", "text/csv; charset=utf-16le")
+                .body(b"n\0,\0t\0\n\0".as_slice());
+        });
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let streamed: Vec<Row> = raxios
+            .get_csv::<Row>("/latin1", None)
+            .await?
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let buffered = raxios.get::<Vec<Row>>("/latin1", None).await?.body;
+        assert_eq!("caf\u{e9}", streamed[0].name);
+        assert_eq!(Some(streamed), buffered);
+
+        assert!(raxios.get_csv::<Row>("/utf16", None).await.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError, Weak},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures_util::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+use reqwest::{header::HeaderMap, Client, Method, Request, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+
+use crate::{NetworkError, Raxios, RaxiosError, RaxiosOptions, RaxiosResponse, RaxiosResult};
+
+/// What makes two requests identical: everything that is sent, plus the timeout
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RequestKey {
+    method: Method,
+    url: String,
+    /// Sorted by name, keeping the order of repeated values
+    headers: Vec<(String, Vec<u8>)>,
+    timeout: Option<Duration>,
+}
+
+impl RequestKey {
+    fn new(request: &Request) -> Self {
+        let mut headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str().to_owned(), value.as_bytes().to_vec()))
+            .collect::<Vec<_>>();
+        headers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Self {
+            method: request.method().clone(),
+            url: request.url().to_string(),
+            headers,
+            timeout: request.timeout().copied(),
+        }
+    }
+}
+
+/// A response which has been read in full, so it can be handed to every caller sharing it
+#[derive(Debug, Clone)]
+struct BufferedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    remote_address: Option<SocketAddr>,
+    raw_body: Option<Bytes>,
+}
+
+/// Why a shared request failed
+#[derive(Debug, Clone)]
+enum SharedError {
+    /// The request could not be sent. The error of the backend can't be cloned, so it is taken
+    /// by the first caller to see it
+    Send(Arc<Mutex<Option<reqwest::Error>>>),
+    Network(NetworkError),
+}
+
+type SharedResponse = Shared<BoxFuture<'static, Result<BufferedResponse, SharedError>>>;
+
+/// One caller's handle on a shared request. Once the last handle of a request which is still in
+/// flight is dropped, the request is cancelled and stops being shared.
+struct Waiter {
+    response: SharedResponse,
+    key: RequestKey,
+    in_flight: Weak<InFlight>,
+}
+
+impl Future for Waiter {
+    type Output = Result<BufferedResponse, SharedError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.response.poll_unpin(cx)
+    }
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        let Some(in_flight) = self.in_flight.upgrade() else {
+            return;
+        };
+        let mut requests = in_flight
+            .requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // New waiters are only added while the lock is held, so nobody else can be left waiting
+        // when the map holds the only other handle
+        let is_last = self.response.strong_count() == Some(2)
+            && matches!(requests.get(&self.key), Some(response) if response.ptr_eq(&self.response));
+        if is_last {
+            requests.remove(&self.key);
+        }
+    }
+}
+
+/// The requests of a `Raxios` instance which are in flight, by what makes them identical
+#[derive(Default)]
+pub(crate) struct InFlight {
+    requests: Mutex<HashMap<RequestKey, SharedResponse>>,
+}
+
+impl Debug for InFlight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let requests = self.requests.lock().unwrap_or_else(PoisonError::into_inner);
+        f.debug_struct("InFlight")
+            .field("requests", &requests.len())
+            .finish()
+    }
+}
+
+impl InFlight {
+    /// Sends `request`, unless an identical request is already in flight, in which case its
+    /// response is shared instead. A request stops being shared once its response has been read,
+    /// or once every caller waiting for it has given up.
+    fn send(self: &Arc<Self>, client: &Client, request: Request) -> Waiter {
+        let key = RequestKey::new(&request);
+        let mut requests = self.requests.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(response) = requests.get(&key) {
+            return Waiter {
+                response: response.clone(),
+                key,
+                in_flight: Arc::downgrade(self),
+            };
+        }
+
+        let in_flight = Arc::downgrade(self);
+        let client = client.clone();
+        let finished_key = key.clone();
+        let response = async move {
+            let response = read_response(client.execute(request).await).await;
+            Self::finish(&in_flight, &finished_key);
+            response
+        }
+        .boxed()
+        .shared();
+
+        requests.insert(key.clone(), response.clone());
+        Waiter {
+            response,
+            key,
+            in_flight: Arc::downgrade(self),
+        }
+    }
+
+    fn finish(in_flight: &Weak<Self>, key: &RequestKey) {
+        if let Some(in_flight) = in_flight.upgrade() {
+            in_flight
+                .requests
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(key);
+        }
+    }
+}
+
+async fn read_response(
+    response: reqwest::Result<Response>,
+) -> Result<BufferedResponse, SharedError> {
+    let response = response.map_err(|err| SharedError::Send(Arc::new(Mutex::new(Some(err)))))?;
+    let response = NetworkError::check(response)
+        .await
+        .map_err(SharedError::Network)?;
+
+    Ok(BufferedResponse {
+        status: response.status(),
+        headers: response.headers().clone(),
+        remote_address: response.remote_addr(),
+        raw_body: response.bytes().await.ok(),
+    })
+}
+
+impl Raxios {
+    /// Whether a GET request with `options` shares one network call with identical requests
+    pub(crate) fn deduplicates(&self, options: &RaxiosOptions) -> bool {
+        options
+            .deduplicate
+            .unwrap_or_else(|| self.defaults().config.deduplicate_gets)
+    }
+
+    /// Sends the request of `builder`, sharing one network call with every identical request in
+    /// flight. Each caller decodes its own copy of the shared response with `options`, and
+    /// receives its own copy of a 4xx or 5xx response.
+    ///
+    /// When the shared request could not be sent, the first caller receives its error and every
+    /// other caller sends its own request, so that each has an `UnableToSendRequest` of its own.
+    pub(crate) async fn send_deduplicated<T>(
+        &self,
+        builder: RequestBuilder,
+        options: &RaxiosOptions,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let request = builder
+            .build()
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+        // Only a request which can be sent again by itself is shared
+        let Some(own_request) = request.try_clone() else {
+            return self.send_alone(request, options).await;
+        };
+
+        let response = match self.in_flight.send(&self.client, request).await {
+            Ok(response) => response,
+            Err(SharedError::Network(err)) => return Err(RaxiosError::NetworkError(err)),
+            Err(SharedError::Send(err)) => {
+                let err = err.lock().unwrap_or_else(PoisonError::into_inner).take();
+                return match err {
+                    Some(err) => Err(RaxiosError::UnableToSendRequest { err }),
+                    None => self.send_alone(own_request, options).await,
+                };
+            }
+        };
+
+        Self::decode_response_with(
+            response.status,
+            &response.headers,
+            response.remote_address,
+            response.raw_body,
+            options.deserialize_body,
+            |raw_body, content_type, media_type| {
+                self.deserialize_response(raw_body, content_type, media_type, Some(options))
+            },
+        )
+    }
+
+    async fn send_alone<T>(
+        &self,
+        request: Request,
+        options: &RaxiosOptions,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let response = self
+            .client
+            .execute(request)
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+        self.response_to_raxios_response(response, options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::future::join_all;
+    use httpmock::prelude::*;
+
+    use crate::{map_string, Raxios, RaxiosConfig, RaxiosError, RaxiosOptions};
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct User {
+        id: u32,
+    }
+
+    fn raxios(server: &MockServer) -> Raxios {
+        Raxios::new(
+            &server.base_url(),
+            Some(RaxiosConfig {
+                deduplicate_gets: true,
+                ..Default::default()
+            }),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_identical_gets_share_one_request() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/users/1").method(GET);
+            then.status(200)
+                .json_body(serde_json::json!({ "id": 1 }))
+                .delay(Duration::from_millis(200));
+        });
+        let raxios = raxios(&server);
+
+        let responses = join_all((0..5).map(|_| raxios.get::<User>("/users/1", None))).await;
+
+        mock.assert_hits(1);
+        for response in responses {
+            let response = response?;
+            assert_eq!(200, response.status);
+            assert_eq!(Some(User { id: 1 }), response.body);
+        }
+
+        // Once the response has been read, the next request is sent again
+        raxios.get::<User>("/users/1", None).await?;
+        mock.assert_hits(2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_different_headers_are_not_shared() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/users/1").method(GET);
+            then.status(200)
+                .json_body(serde_json::json!({ "id": 1 }))
+                .delay(Duration::from_millis(200));
+        });
+        let raxios = raxios(&server);
+        let with_tenant = |tenant: &str| RaxiosOptions {
+            headers: Some(map_string! {"x-tenant" => tenant}.into()),
+            ..Default::default()
+        };
+
+        let (first, second, third) = tokio::join!(
+            raxios.get::<User>("/users/1", Some(with_tenant("acme"))),
+            raxios.get::<User>("/users/1", Some(with_tenant("globex"))),
+            raxios.get::<User>("/users/1", Some(with_tenant("acme"))),
+        );
+
+        first?;
+        second?;
+        third?;
+        mock.assert_hits(2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_shared() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/users/1").method(GET);
+            then.status(503)
+                .body("unavailable")
+                .delay(Duration::from_millis(200));
+        });
+        let raxios = raxios(&server);
+
+        let responses = join_all((0..3).map(|_| raxios.get::<User>("/users/1", None))).await;
+
+        mock.assert_hits(1);
+        for response in responses {
+            match response {
+                Err(RaxiosError::NetworkError(err)) => {
+                    assert_eq!(503, err.status_code);
+                    assert_eq!(Some("unavailable".into()), err.raw_body);
+                }
+                other => panic!("Expected a NetworkError, got {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_errors_are_unable_to_send_request() {
+        let raxios = Raxios::new(
+            "http://127.0.0.1:1",
+            Some(RaxiosConfig {
+                deduplicate_gets: true,
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        let responses = join_all((0..3).map(|_| raxios.get::<User>("/users/1", None))).await;
+
+        for response in responses {
+            let err = response.unwrap_err();
+            assert!(matches!(err, RaxiosError::UnableToSendRequest { .. }));
+            assert!(err.is_connect());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_request_is_sent_again() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/users/1").method(GET);
+            then.status(200)
+                .json_body(serde_json::json!({ "id": 1 }))
+                .delay(Duration::from_millis(200));
+        });
+        let raxios = raxios(&server);
+
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(50),
+            raxios.get::<User>("/users/1", None),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        assert_eq!(0, raxios.in_flight.requests.lock().unwrap().len());
+
+        let response = raxios.get::<User>("/users/1", None).await?;
+        assert_eq!(Some(User { id: 1 }), response.body);
+        mock.assert_hits(2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deduplication_can_be_disabled_per_request() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/users/1").method(GET);
+            then.status(200)
+                .json_body(serde_json::json!({ "id": 1 }))
+                .delay(Duration::from_millis(200));
+        });
+        let raxios = raxios(&server);
+        let options = || {
+            Some(RaxiosOptions {
+                deduplicate: Some(false),
+                ..Default::default()
+            })
+        };
+
+        let responses = join_all((0..3).map(|_| raxios.get::<User>("/users/1", options()))).await;
+
+        mock.assert_hits(3);
+        for response in responses {
+            response?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,19 @@
+use reqwest::{
+    header::{InvalidHeaderName, InvalidHeaderValue},
+    StatusCode,
+};
 use thiserror::Error;
 
-use crate::network_error::NetworkError;
+use crate::{
+    graphql::GraphQLErrors, json_rpc::JsonRpcError, network_error::NetworkError, raw::RawError,
+    soap::SoapFault,
+};
 
 pub type RaxiosResult<T> = Result<T, RaxiosError>;
 
 /// Represents an error that occurs when attempting to deserialize a response body into type T
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum DeserializationError {
     #[error(transparent)]
     Json(serde_json::Error),
@@ -13,12 +21,38 @@ pub enum DeserializationError {
     Xml(serde_xml_rs::Error),
     #[error(transparent)]
     UrlEncoded(serde_urlencoded::de::Error),
+    #[error(transparent)]
+    Raw(RawError),
+    #[cfg(feature = "msgpack")]
+    #[error(transparent)]
+    MessagePack(rmp_serde::decode::Error),
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    Cbor(ciborium::de::Error<std::io::Error>),
+    #[cfg(feature = "yaml")]
+    #[error(transparent)]
+    Yaml(serde_yaml::Error),
+    #[cfg(feature = "toml")]
+    #[error(transparent)]
+    Toml(toml::de::Error),
+    #[cfg(feature = "protobuf")]
+    #[error(transparent)]
+    Protobuf(prost::DecodeError),
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    Csv(csv::Error),
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    CsvRow(csv::DeserializeError),
+    #[error("Unable to deserialize a response with content-type {0}")]
+    UnsupportedContentType(String),
     #[error("{0}")]
     Unknown(String),
 }
 
 /// Represents an error that occurs when attempting to serialize a request body into type T
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum SerializationError {
     #[error(transparent)]
     Json(serde_json::Error),
@@ -26,11 +60,31 @@ pub enum SerializationError {
     Xml(serde_xml_rs::Error),
     #[error(transparent)]
     UrlEncoded(serde_urlencoded::ser::Error),
+    #[error(transparent)]
+    Raw(RawError),
+    #[cfg(feature = "msgpack")]
+    #[error(transparent)]
+    MessagePack(rmp_serde::encode::Error),
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    Cbor(ciborium::ser::Error<std::io::Error>),
+    #[cfg(feature = "yaml")]
+    #[error(transparent)]
+    Yaml(serde_yaml::Error),
+    #[cfg(feature = "toml")]
+    #[error(transparent)]
+    Toml(toml::ser::Error),
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    Csv(csv::Error),
+    #[error("Unable to serialize a request body as content-type {0}")]
+    UnsupportedContentType(String),
     #[error("{0}")]
     Unknown(String),
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum RaxiosError {
     #[error(transparent)]
     Unknown(anyhow::Error),
@@ -46,4 +100,229 @@ pub enum RaxiosError {
     SerializationError(SerializationError),
     #[error(transparent)]
     DeserializationError(DeserializationError),
+    #[error(transparent)]
+    GraphQL(GraphQLErrors),
+    #[error(transparent)]
+    JsonRpc(JsonRpcError),
+    #[error(transparent)]
+    SoapFault(Box<SoapFault>),
+}
+
+/// A stable, backend-independent classification of a `RaxiosError`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The request timed out before a response was received
+    Timeout,
+    /// A connection to the remote server could not be established
+    Connect,
+    /// The request could not be built or sent
+    Request,
+    /// The redirect policy was violated
+    Redirect,
+    /// The request or response body could not be read or written
+    Body,
+    /// The remote server responded with a 4xx or 5xx status code
+    Status,
+    /// The url could not be built
+    InvalidUrl,
+    /// A header name or value was invalid
+    InvalidHeader,
+    /// The request body could not be serialized
+    Serialization,
+    /// The response body could not be deserialized
+    Deserialization,
+    /// The GraphQL server responded with errors
+    GraphQL,
+    /// The JSON-RPC server responded with an error object
+    JsonRpc,
+    /// The SOAP service responded with a fault
+    SoapFault,
+    /// Anything else
+    Unknown,
+}
+
+impl RaxiosError {
+    /// Classifies this error without exposing the underlying HTTP backend
+    ///
+    /// # Example
+    /// ```rust
+    /// use raxios::{ErrorKind, RaxiosError};
+    ///
+    /// let err = RaxiosError::InvalidUrl(String::from("not a url"));
+    /// assert_eq!(ErrorKind::InvalidUrl, err.kind());
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            RaxiosError::Unknown(_) => ErrorKind::Unknown,
+            RaxiosError::HeaderParseError(_, _) => ErrorKind::InvalidHeader,
+            RaxiosError::InvalidUrl(_) => ErrorKind::InvalidUrl,
+            RaxiosError::UnableToSendRequest { err } => {
+                if err.is_timeout() {
+                    ErrorKind::Timeout
+                } else if err.is_connect() {
+                    ErrorKind::Connect
+                } else if err.is_redirect() {
+                    ErrorKind::Redirect
+                } else if err.is_status() {
+                    ErrorKind::Status
+                } else if err.is_body() {
+                    ErrorKind::Body
+                } else if err.is_decode() {
+                    ErrorKind::Deserialization
+                } else if err.is_builder() && is_invalid_header(err) {
+                    ErrorKind::InvalidHeader
+                } else {
+                    ErrorKind::Request
+                }
+            }
+            RaxiosError::NetworkError(_) => ErrorKind::Status,
+            RaxiosError::SerializationError(_) => ErrorKind::Serialization,
+            RaxiosError::DeserializationError(_) => ErrorKind::Deserialization,
+            RaxiosError::GraphQL(_) => ErrorKind::GraphQL,
+            RaxiosError::JsonRpc(_) => ErrorKind::JsonRpc,
+            RaxiosError::SoapFault(_) => ErrorKind::SoapFault,
+        }
+    }
+
+    /// The HTTP status code returned by the remote server, if one was received
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            RaxiosError::NetworkError(err) => Some(err.status_code),
+            RaxiosError::GraphQL(err) => Some(err.status_code),
+            RaxiosError::SoapFault(err) => Some(err.status_code),
+            RaxiosError::UnableToSendRequest { err } => err.status(),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the request timed out
+    pub fn is_timeout(&self) -> bool {
+        self.kind() == ErrorKind::Timeout
+    }
+
+    /// Returns true if a connection to the remote server could not be established
+    pub fn is_connect(&self) -> bool {
+        self.kind() == ErrorKind::Connect
+    }
+
+    /// Returns true if the remote server responded with a 4xx status code
+    pub fn is_client_error(&self) -> bool {
+        self.status()
+            .map(|status| status.is_client_error())
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the remote server responded with a 5xx status code
+    pub fn is_server_error(&self) -> bool {
+        self.status()
+            .map(|status| status.is_server_error())
+            .unwrap_or(false)
+    }
+
+    /// Returns true if sending the same request again might succeed.
+    ///
+    /// Timeouts, connection failures, `408 Request Timeout`, `429 Too Many Requests`,
+    /// `500`, `502`, `503` and `504` are considered retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self.kind() {
+            ErrorKind::Timeout | ErrorKind::Connect => true,
+            _ => matches!(
+                self.status(),
+                Some(
+                    StatusCode::REQUEST_TIMEOUT
+                        | StatusCode::TOO_MANY_REQUESTS
+                        | StatusCode::INTERNAL_SERVER_ERROR
+                        | StatusCode::BAD_GATEWAY
+                        | StatusCode::SERVICE_UNAVAILABLE
+                        | StatusCode::GATEWAY_TIMEOUT
+                )
+            ),
+        }
+    }
+}
+
+/// Returns true if the backend refused to build a request because of a header name or value
+fn is_invalid_header(err: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<http::Error>() {
+            return err.is::<InvalidHeaderName>() || err.is::<InvalidHeaderValue>();
+        }
+        if err.is::<InvalidHeaderName>() || err.is::<InvalidHeaderValue>() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::{ErrorKind, RaxiosError, SerializationError};
+    use crate::NetworkError;
+
+    fn network_error(status_code: StatusCode) -> RaxiosError {
+        RaxiosError::NetworkError(NetworkError {
+            status_code,
+            origin_address: None,
+            raw_body: None,
+            response_headers: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_network_error_classification() {
+        let err = network_error(StatusCode::NOT_FOUND);
+        assert_eq!(ErrorKind::Status, err.kind());
+        assert_eq!(Some(StatusCode::NOT_FOUND), err.status());
+        assert!(err.is_client_error());
+        assert!(!err.is_server_error());
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_retryable_status_codes() {
+        assert!(network_error(StatusCode::SERVICE_UNAVAILABLE).is_retryable());
+        assert!(network_error(StatusCode::TOO_MANY_REQUESTS).is_retryable());
+        assert!(!network_error(StatusCode::NOT_IMPLEMENTED).is_retryable());
+    }
+
+    #[test]
+    fn test_non_network_errors_have_no_status() {
+        let err = RaxiosError::SerializationError(SerializationError::Unknown(String::new()));
+        assert_eq!(ErrorKind::Serialization, err.kind());
+        assert_eq!(None, err.status());
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_connect_error_is_retryable() {
+        let raxios = crate::Raxios::new("http://127.0.0.1:1", None).unwrap();
+        let err = raxios.get::<()>("/", None).await.unwrap_err();
+
+        assert!(err.is_connect());
+        assert!(err.is_retryable());
+        assert_eq!(None, err.status());
+    }
+
+    #[tokio::test]
+    async fn test_builder_errors() {
+        let client = reqwest::Client::new();
+
+        let err = client
+            .get("http://127.0.0.1:1")
+            .header("not a header", "value")
+            .send()
+            .await
+            .unwrap_err();
+        let err = RaxiosError::UnableToSendRequest { err };
+        assert_eq!(ErrorKind::InvalidHeader, err.kind());
+
+        let err = client.get("not a url").send().await.unwrap_err();
+        let err = RaxiosError::UnableToSendRequest { err };
+        assert_eq!(ErrorKind::Request, err.kind());
+    }
 }
@@ -0,0 +1,435 @@
+use std::fmt::Display;
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{
+    ContentType, DeserializationError, Raxios, RaxiosError, RaxiosOptions, RaxiosResponse,
+    RaxiosResult,
+};
+
+const GRAPHQL_ACCEPT: &str = "application/graphql-response+json, application/json";
+
+/// A GraphQL operation, sent as `{query, variables, operationName, extensions}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphQLRequest<V = Value> {
+    pub query: String,
+    pub variables: Option<V>,
+    /// Which operation of `query` to execute, when it contains more than one
+    pub operation_name: Option<String>,
+    /// Extra protocol extensions. `persistedQuery` is added when `persisted` is set
+    pub extensions: Option<serde_json::Map<String, Value>>,
+    /// Use Automatic Persisted Queries: the SHA-256 hash of `query` is sent first, and `query`
+    /// itself is only sent if the server has not seen it before
+    pub persisted: bool,
+}
+
+impl<V> GraphQLRequest<V> {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            variables: None,
+            operation_name: None,
+            extensions: None,
+            persisted: false,
+        }
+    }
+
+    /// The lowercase hex SHA-256 hash of `query`, which identifies it as a persisted query
+    pub fn query_hash(&self) -> String {
+        Sha256::digest(self.query.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLBody<'a, V> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variables: Option<&'a V>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    operation_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extensions: Option<serde_json::Map<String, Value>>,
+}
+
+#[derive(Deserialize, Default)]
+struct GraphQLResponse {
+    #[serde(default)]
+    data: Option<Value>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    errors: Vec<GraphQLError>,
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Option::<T>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
+/// A line and column in the GraphQL document which an error relates to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct GraphQLLocation {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A segment of the path to the field of the response which an error relates to
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum GraphQLPathSegment {
+    Field(String),
+    Index(usize),
+}
+
+/// A single entry of the `errors` array of a GraphQL response
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub locations: Vec<GraphQLLocation>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub path: Vec<GraphQLPathSegment>,
+    #[serde(default)]
+    pub extensions: Option<Value>,
+}
+
+impl GraphQLError {
+    /// The `extensions.code` of this error, such as `UNAUTHENTICATED`
+    pub fn code(&self) -> Option<&str> {
+        self.extensions.as_ref()?.get("code")?.as_str()
+    }
+}
+
+/// Represents a GraphQL response with a non-empty `errors` array, whatever its HTTP status
+#[derive(Error, Debug)]
+pub struct GraphQLErrors {
+    pub status_code: StatusCode,
+    pub errors: Vec<GraphQLError>,
+    /// Any partial `data` which was returned alongside the errors
+    pub data: Option<Value>,
+}
+
+impl Display for GraphQLErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let messages = self
+            .errors
+            .iter()
+            .map(|error| error.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        write!(
+            f,
+            "GraphQL Error -- Status: {}, Errors: {}",
+            self.status_code, messages
+        )
+    }
+}
+
+impl GraphQLErrors {
+    fn is_persisted_query_not_found(&self) -> bool {
+        self.errors.iter().any(|error| {
+            error.message == "PersistedQueryNotFound"
+                || error.code() == Some("PERSISTED_QUERY_NOT_FOUND")
+        })
+    }
+}
+
+impl Raxios {
+    async fn post_graphql<T, V>(
+        &self,
+        endpoint: &str,
+        body: &GraphQLBody<'_, V>,
+        options: &RaxiosOptions,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+        V: Serialize,
+    {
+        let response = match self
+            .post::<GraphQLResponse, _>(endpoint, Some(body), Some(options.clone()))
+            .await
+        {
+            Ok(response) => response,
+            // Servers following the GraphQL over HTTP spec answer some failed requests with a 4xx
+            // status, but still describe the problem in `errors`
+            Err(RaxiosError::NetworkError(err)) => {
+                let errors = err
+                    .raw_body
+                    .as_ref()
+                    .and_then(|raw_body| serde_json::from_slice::<GraphQLResponse>(raw_body).ok())
+                    .filter(|response| !response.errors.is_empty());
+                return Err(match errors {
+                    Some(response) => RaxiosError::GraphQL(GraphQLErrors {
+                        status_code: err.status_code,
+                        errors: response.errors,
+                        data: response.data,
+                    }),
+                    None => RaxiosError::NetworkError(err),
+                });
+            }
+            Err(err) => return Err(err),
+        };
+
+        let RaxiosResponse {
+            body,
+            raw_body,
+            status,
+            response_headers,
+            remote_address,
+        } = response;
+        let body = body.unwrap_or_default();
+        if !body.errors.is_empty() {
+            return Err(RaxiosError::GraphQL(GraphQLErrors {
+                status_code: status,
+                errors: body.errors,
+                data: body.data,
+            }));
+        }
+
+        let data = body
+            .data
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))?;
+        Ok(RaxiosResponse {
+            body: data,
+            raw_body,
+            status,
+            response_headers,
+            remote_address,
+        })
+    }
+
+    /// Sends a GraphQL operation as an HTTP POST request with a JSON body, and deserializes its
+    /// `data` into `T`.
+    ///
+    /// A response with a non-empty `errors` array is returned as `RaxiosError::GraphQL`, even when
+    /// its status is 200. When `request.persisted` is set, the hash of the query is sent first,
+    /// and the request is repeated with the full query if the server answers
+    /// `PersistedQueryNotFound`.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `request` - The query, its variables and operation name
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use raxios::{GraphQLRequest, Raxios};
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct Viewer {
+    ///     login: String,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct Data {
+    ///     viewer: Viewer,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/graphql")
+    ///             .method(POST)
+    ///             .json_body(serde_json::json!({
+    ///                 "query": "query Viewer { viewer { login } }",
+    ///                 "operationName": "Viewer"
+    ///             }));
+    ///         then.status(200)
+    ///             .json_body(serde_json::json!({"data": {"viewer": {"login": "octocat"}}}));
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let request = GraphQLRequest::<()> {
+    ///         operation_name: Some(String::from("Viewer")),
+    ///         ..GraphQLRequest::new("query Viewer { viewer { login } }")
+    ///     };
+    ///     let response = raxios.graphql::<Data, _>("/graphql", &request, None).await.unwrap();
+    ///
+    ///     assert_eq!("octocat", response.body.unwrap().viewer.login);
+    /// }
+    /// ```
+    pub async fn graphql<T, V>(
+        &self,
+        endpoint: &str,
+        request: &GraphQLRequest<V>,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+        V: Serialize,
+    {
+        let mut options = options.unwrap_or_default();
+        options.content_type = Some(ContentType::Json);
+        options
+            .accept
+            .get_or_insert_with(|| ContentType::Other(String::from(GRAPHQL_ACCEPT)));
+
+        let mut body = GraphQLBody {
+            query: Some(&request.query),
+            variables: request.variables.as_ref(),
+            operation_name: request.operation_name.as_deref(),
+            extensions: request.extensions.clone(),
+        };
+        if request.persisted {
+            body.extensions.get_or_insert_with(Default::default).insert(
+                String::from("persistedQuery"),
+                json!({"version": 1, "sha256Hash": request.query_hash()}),
+            );
+
+            let hash_only = GraphQLBody {
+                query: None,
+                ..body
+            };
+            match self.post_graphql(endpoint, &hash_only, &options).await {
+                Err(RaxiosError::GraphQL(errors)) if errors.is_persisted_query_not_found() => {}
+                response => return response,
+            }
+            body = GraphQLBody {
+                query: Some(&request.query),
+                ..hash_only
+            };
+        }
+
+        self.post_graphql(endpoint, &body, &options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    use super::{GraphQLLocation, GraphQLPathSegment, GraphQLRequest};
+    use crate::{ErrorKind, Raxios, RaxiosError};
+
+    #[test]
+    fn test_query_hash() {
+        assert_eq!(
+            "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38",
+            GraphQLRequest::<()>::new("{__typename}").query_hash()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_on_200() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/graphql").method(POST).json_body_partial(
+                r#"{"query": "query($id: ID!) { user(id: $id) { name } }", "variables": {"id": "7"}}"#,
+            );
+            then.status(200).json_body(json!({
+                "data": {"user": null},
+                "errors": [{
+                    "message": "Not allowed",
+                    "locations": [{"line": 1, "column": 20}],
+                    "path": ["user", 0],
+                    "extensions": {"code": "FORBIDDEN"}
+                }]
+            }));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let request = GraphQLRequest {
+            variables: Some(json!({"id": "7"})),
+            ..GraphQLRequest::new("query($id: ID!) { user(id: $id) { name } }")
+        };
+        let err = raxios
+            .graphql::<serde_json::Value, _>("/graphql", &request, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(ErrorKind::GraphQL, err.kind());
+        if let RaxiosError::GraphQL(errors) = err {
+            assert_eq!(200, errors.status_code);
+            assert_eq!(Some(json!({"user": null})), errors.data);
+            let error = &errors.errors[0];
+            assert_eq!("Not allowed", error.message);
+            assert_eq!(Some("FORBIDDEN"), error.code());
+            assert_eq!(
+                vec![GraphQLLocation {
+                    line: 1,
+                    column: 20
+                }],
+                error.locations
+            );
+            assert_eq!(
+                vec![
+                    GraphQLPathSegment::Field(String::from("user")),
+                    GraphQLPathSegment::Index(0)
+                ],
+                error.path
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_on_4xx() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/graphql");
+            then.status(400)
+                .header("content-type", "application/graphql-response+json")
+                .body(r#"{"errors": [{"message": "Syntax Error", "path": null}]}"#);
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let err = raxios
+            .graphql::<serde_json::Value, ()>("/graphql", &GraphQLRequest::new("{"), None)
+            .await
+            .unwrap_err();
+
+        assert!(err.is_client_error());
+        assert!(
+            matches!(err, RaxiosError::GraphQL(ref errors) if errors.errors[0].path.is_empty())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphql_persisted_query() {
+        let server = MockServer::start();
+        let request = GraphQLRequest::<()> {
+            persisted: true,
+            ..GraphQLRequest::new("{__typename}")
+        };
+        let extensions =
+            json!({"persistedQuery": {"version": 1, "sha256Hash": request.query_hash()}});
+
+        let miss = server.mock(|when, then| {
+            when.path("/graphql")
+                .json_body(json!({"extensions": extensions.clone()}));
+            then.status(200).json_body(json!({
+                "errors": [{"message": "PersistedQueryNotFound"}]
+            }));
+        });
+        let register = server.mock(|when, then| {
+            when.path("/graphql")
+                .json_body(json!({"query": "{__typename}", "extensions": extensions.clone()}));
+            then.status(200)
+                .json_body(json!({"data": {"__typename": "Query"}}));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let response = raxios
+            .graphql::<serde_json::Value, _>("/graphql", &request, None)
+            .await
+            .unwrap();
+
+        assert_eq!(Some(json!({"__typename": "Query"})), response.body);
+        miss.assert();
+        register.assert();
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+/// An ordered, case-insensitive, multi-valued map of HTTP headers.
+///
+/// Header names are stored in lowercase, and every value of a header which appears more than
+/// once (such as `set-cookie` or `link`) is kept in the order it was received. Values are opaque
+/// bytes, since HTTP allows header values which are not valid UTF-8.
+///
+/// # Example
+/// ```rust
+/// use raxios::{map_string, RaxiosHeaders};
+///
+/// let mut headers: RaxiosHeaders = map_string! {"X-Tenant" => "acme"}.into();
+/// headers.append("Set-Cookie", "a=1");
+/// headers.append("set-cookie", "b=2");
+///
+/// assert_eq!(Some("acme"), headers.get("x-tenant"));
+/// assert_eq!(vec!["a=1", "b=2"], headers.get_all("SET-COOKIE").collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RaxiosHeaders {
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl RaxiosHeaders {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The first value of `name` if it is valid UTF-8
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_bytes(name)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// The first value of `name`
+    pub fn get_bytes(&self, name: &str) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_slice())
+    }
+
+    /// Every value of `name` which is valid UTF-8, in order
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.get_all_bytes(name)
+            .filter_map(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// Every value of `name`, in order
+    pub fn get_all_bytes<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_slice())
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get_bytes(name).is_some()
+    }
+
+    /// Sets `name` to `value`, replacing every existing value of `name`
+    pub fn insert(&mut self, name: impl AsRef<str>, value: impl AsRef<[u8]>) -> &mut Self {
+        self.remove(name.as_ref());
+        self.append(name, value)
+    }
+
+    /// Adds `value` to `name`, keeping any existing values of `name`
+    pub fn append(&mut self, name: impl AsRef<str>, value: impl AsRef<[u8]>) -> &mut Self {
+        self.entries
+            .push((name.as_ref().to_ascii_lowercase(), value.as_ref().to_vec()));
+        self
+    }
+
+    /// Removes every value of `name`, returning whether there were any
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.entries.len();
+        self.entries
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+        len != self.entries.len()
+    }
+
+    /// Replaces the values of every header in `other` with the values from `other`.
+    /// Headers which are not in `other` are kept.
+    pub fn merge(&mut self, other: &RaxiosHeaders) {
+        for (name, _) in &other.entries {
+            self.remove(name);
+        }
+        self.entries.extend(other.entries.iter().cloned());
+    }
+
+    /// Iterates over every name and value, in order. Names are lowercase.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.entries
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_slice()))
+    }
+
+    /// The number of values, counting every value of a repeated header
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for RaxiosHeaders
+where
+    K: AsRef<str>,
+    V: AsRef<[u8]>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut headers = Self::new();
+        for (name, value) in iter {
+            headers.append(name, value);
+        }
+        headers
+    }
+}
+
+/// Converts the output of the `map_string!` macro. Headers are sorted by name, so that the order
+/// they are sent in is stable.
+impl From<HashMap<String, String>> for RaxiosHeaders {
+    fn from(map: HashMap<String, String>) -> Self {
+        let mut headers: Self = map.into_iter().collect();
+        headers.entries.sort();
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RaxiosHeaders;
+
+    #[test]
+    fn test_headers_are_case_insensitive_and_multi_valued() {
+        let mut headers = RaxiosHeaders::new();
+        headers
+            .append("Link", "<a>; rel=next")
+            .append("Vary", "accept")
+            .append("link", "<b>; rel=last");
+
+        assert_eq!(Some("<a>; rel=next"), headers.get("LINK"));
+        assert_eq!(
+            vec!["<a>; rel=next", "<b>; rel=last"],
+            headers.get_all("link").collect::<Vec<_>>()
+        );
+
+        headers.insert("LINK", "<c>");
+        assert_eq!(vec!["<c>"], headers.get_all("link").collect::<Vec<_>>());
+        assert_eq!(2, headers.len());
+    }
+
+    #[test]
+    fn test_headers_tolerate_opaque_values() {
+        let mut headers = RaxiosHeaders::new();
+        headers.append("x-opaque", [0xff, 0xfe]);
+
+        assert!(headers.contains_key("X-Opaque"));
+        assert_eq!(None, headers.get("x-opaque"));
+        assert_eq!(Some(&[0xff, 0xfe][..]), headers.get_bytes("x-opaque"));
+    }
+
+    #[test]
+    fn test_merge_replaces_headers_by_name() {
+        let mut headers: RaxiosHeaders = [("accept", "a"), ("x-keep", "k"), ("x-multi", "1")]
+            .into_iter()
+            .collect();
+        let other: RaxiosHeaders = [("Accept", "b"), ("x-multi", "2"), ("x-multi", "3")]
+            .into_iter()
+            .collect();
+        headers.merge(&other);
+
+        assert_eq!(Some("b"), headers.get("accept"));
+        assert_eq!(Some("k"), headers.get("x-keep"));
+        assert_eq!(
+            vec!["2", "3"],
+            headers.get_all("x-multi").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_from_map_string() {
+        let headers: RaxiosHeaders = crate::map_string! {"B" => "2", "a" => "1"}.into();
+        assert_eq!(
+            vec![("a", &b"1"[..]), ("b", &b"2"[..])],
+            headers.iter().collect::<Vec<_>>()
+        );
+    }
+}
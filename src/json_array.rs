@@ -0,0 +1,335 @@
+use bytes::{Bytes, BytesMut};
+use futures_util::{stream, Stream, StreamExt};
+use serde::de::{DeserializeOwned, Error};
+
+use crate::{
+    ContentType, DeserializationError, Raxios, RaxiosError, RaxiosOptions, RaxiosResult,
+    RaxiosStream,
+};
+
+fn malformed(message: &str) -> RaxiosError {
+    RaxiosError::DeserializationError(DeserializationError::Json(serde_json::Error::custom(
+        message,
+    )))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// Waiting for the opening `[`
+    Start,
+    /// Waiting for the start of the next element, or the closing `]` if no element has been seen
+    BeforeElement { first: bool },
+    /// Scanning an element which starts at the beginning of the buffer
+    InElement {
+        position: usize,
+        depth: usize,
+        in_string: bool,
+        escaped: bool,
+    },
+    /// The closing `]` has been seen, and only whitespace may follow
+    Done,
+}
+
+/// Finds the boundaries of the elements of a top-level JSON array without parsing them.
+/// Only the element currently being scanned is held in memory.
+struct ArraySplitter {
+    buffer: BytesMut,
+    state: State,
+}
+
+impl ArraySplitter {
+    fn new() -> Self {
+        Self {
+            buffer: BytesMut::new(),
+            state: State::Start,
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Option<u8> {
+        let start = self
+            .buffer
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())
+            .unwrap_or(self.buffer.len());
+        let _ = self.buffer.split_to(start);
+        self.buffer.first().copied()
+    }
+
+    /// Returns the next complete element, `Ok(None)` if more input is needed, or an error if the
+    /// input is not a JSON array
+    fn next_element(&mut self) -> RaxiosResult<Option<Bytes>> {
+        loop {
+            match self.state {
+                State::Done => {
+                    return match self.skip_whitespace() {
+                        None => Ok(None),
+                        Some(_) => Err(malformed("Unexpected data after the end of JSON array")),
+                    }
+                }
+                State::Start => match self.skip_whitespace() {
+                    None => return Ok(None),
+                    Some(b'[') => {
+                        let _ = self.buffer.split_to(1);
+                        self.state = State::BeforeElement { first: true };
+                    }
+                    Some(_) => return Err(malformed("Response body is not a JSON array")),
+                },
+                State::BeforeElement { first } => match self.skip_whitespace() {
+                    None => return Ok(None),
+                    Some(b']') if first => {
+                        let _ = self.buffer.split_to(1);
+                        self.state = State::Done;
+                    }
+                    Some(b']') => return Err(malformed("Unexpected `]` after `,` in JSON array")),
+                    Some(b',') => return Err(malformed("Unexpected `,` in JSON array")),
+                    Some(_) => {
+                        self.state = State::InElement {
+                            position: 0,
+                            depth: 0,
+                            in_string: false,
+                            escaped: false,
+                        }
+                    }
+                },
+                State::InElement {
+                    mut position,
+                    mut depth,
+                    mut in_string,
+                    mut escaped,
+                } => {
+                    while position < self.buffer.len() {
+                        let byte = self.buffer[position];
+                        if in_string {
+                            if escaped {
+                                escaped = false;
+                            } else if byte == b'\\' {
+                                escaped = true;
+                            } else if byte == b'"' {
+                                in_string = false;
+                            }
+                        } else {
+                            match byte {
+                                b'"' => in_string = true,
+                                b'{' | b'[' => depth += 1,
+                                b'}' | b']' if depth > 0 => depth -= 1,
+                                b']' | b',' if depth == 0 => {
+                                    let element = self.buffer.split_to(position).freeze();
+                                    let _ = self.buffer.split_to(1);
+                                    self.state = if byte == b']' {
+                                        State::Done
+                                    } else {
+                                        State::BeforeElement { first: false }
+                                    };
+                                    return Ok(Some(element));
+                                }
+                                b'}' => return Err(malformed("Unexpected `}` in JSON array")),
+                                _ => {}
+                            }
+                        }
+                        position += 1;
+                    }
+                    self.state = State::InElement {
+                        position,
+                        depth,
+                        in_string,
+                        escaped,
+                    };
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// Splits a streamed top-level JSON array into the raw bytes of each of its elements as bytes arrive
+pub(crate) fn split_array_elements<S>(body: S) -> impl Stream<Item = RaxiosResult<Bytes>> + Send
+where
+    S: Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+{
+    let state = (Box::pin(body), ArraySplitter::new(), false);
+
+    stream::unfold(state, |(mut body, mut splitter, failed)| async move {
+        if failed {
+            return None;
+        }
+        loop {
+            match splitter.next_element() {
+                Ok(Some(element)) => return Some((Ok(element), (body, splitter, false))),
+                Ok(None) => {}
+                Err(err) => return Some((Err(err), (body, splitter, true))),
+            }
+
+            match body.next().await {
+                Some(Ok(chunk)) => splitter.buffer.extend_from_slice(&chunk),
+                Some(Err(err)) => {
+                    return Some((
+                        Err(RaxiosError::UnableToSendRequest { err }),
+                        (body, splitter, true),
+                    ))
+                }
+                None if splitter.state == State::Done => return None,
+                None => {
+                    return Some((
+                        Err(malformed("Unexpected end of JSON array")),
+                        (body, splitter, true),
+                    ))
+                }
+            }
+        }
+    })
+}
+
+impl Raxios {
+    /// Sends an HTTP GET request whose response is a single top-level JSON array, and streams each
+    /// element of the array as it arrives. Memory use is bounded by the size of the largest element
+    /// rather than the size of the response.
+    ///
+    /// An element which fails to deserialize yields an `Err` without ending the stream. A response
+    /// which is not a JSON array yields a single `Err`.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures_util::StreamExt;
+    /// use httpmock::prelude::*;
+    /// use raxios::Raxios;
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct User {
+    ///     id: u32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/users").method(GET);
+    ///         then.status(200).body("[{\"id\": 1}, {\"id\": 2}]");
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let users: Vec<User> = raxios
+    ///         .get_json_array::<User>("/users", None)
+    ///         .await
+    ///         .unwrap()
+    ///         .map(Result::unwrap)
+    ///         .collect()
+    ///         .await;
+    ///
+    ///     assert_eq!(vec![User { id: 1 }, User { id: 2 }], users);
+    /// }
+    /// ```
+    pub async fn get_json_array<T>(
+        &self,
+        endpoint: &str,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosStream<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut options = options.unwrap_or_default();
+        options.accept.get_or_insert(ContentType::Json);
+
+        let response = self
+            .build_raw_request::<Vec<u8>>(
+                None,
+                Some(&options),
+                self.client.get(self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+        let response = self.check_response_and_return_err(response).await?;
+
+        Ok(Box::pin(split_array_elements(response.bytes_stream()).map(
+            |element| {
+                serde_json::from_slice::<T>(&element?)
+                    .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::{stream, StreamExt};
+
+    use super::split_array_elements;
+
+    async fn split(chunks: &[&'static str]) -> Vec<Result<String, String>> {
+        let chunks: Vec<reqwest::Result<Bytes>> = chunks
+            .iter()
+            .map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())))
+            .collect();
+        split_array_elements(stream::iter(chunks))
+            .map(|element| {
+                element
+                    .map(|bytes| String::from_utf8(bytes.to_vec()).unwrap())
+                    .map_err(|e| e.to_string())
+            })
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_split_nested_elements_across_chunks() {
+        let elements = split(&[
+            " [ {\"a\": [1, 2], \"b\": \"x,]}\\\"",
+            "\"}, 42,\"str\" , [[]] ,null]  ",
+        ])
+        .await;
+
+        assert_eq!(
+            vec![
+                Ok(String::from("{\"a\": [1, 2], \"b\": \"x,]}\\\"\"}")),
+                Ok(String::from("42")),
+                Ok(String::from("\"str\" ")),
+                Ok(String::from("[[]] ")),
+                Ok(String::from("null")),
+            ],
+            elements
+        );
+    }
+
+    #[tokio::test]
+    async fn test_split_empty_array() {
+        assert!(split(&["[", " ]"]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_split_rejects_non_arrays() {
+        let elements = split(&["{\"a\": 1}"]).await;
+        assert_eq!(1, elements.len());
+        assert!(elements[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_split_truncated_array() {
+        let elements = split(&["[1, 2"]).await;
+        assert_eq!(Ok(String::from("1")), elements[0]);
+        assert!(elements[1].is_err());
+        assert_eq!(2, elements.len());
+    }
+
+    #[tokio::test]
+    async fn test_split_rejects_trailing_comma() {
+        let elements = split(&["[1,", " ]"]).await;
+        assert_eq!(Ok(String::from("1")), elements[0]);
+        assert!(elements[1].is_err());
+        assert_eq!(2, elements.len());
+    }
+
+    #[tokio::test]
+    async fn test_split_rejects_data_after_array() {
+        let elements = split(&["[1] ", " [2]"]).await;
+        assert_eq!(Ok(String::from("1")), elements[0]);
+        assert!(elements[1].is_err());
+        assert_eq!(2, elements.len());
+
+        assert!(split(&["[] x"]).await[0].is_err());
+    }
+}
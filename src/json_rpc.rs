@@ -0,0 +1,444 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::{
+    ContentType, DeserializationError, Raxios, RaxiosError, RaxiosOptions, RaxiosResult,
+    SerializationError,
+};
+
+/// The error object of a JSON-RPC 2.0 response
+#[derive(Error, Debug, Clone, PartialEq, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default)]
+    pub data: Option<Value>,
+}
+
+impl Display for JsonRpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "JSON-RPC Error -- Code: {}, Message: {}",
+            self.code, self.message
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse {
+    #[serde(default)]
+    id: Value,
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    fn into_result(self) -> Result<Value, JsonRpcError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.result),
+        }
+    }
+}
+
+fn deserialize_json<T>(value: Value) -> RaxiosResult<T>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_value(value)
+        .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))
+}
+
+/// Builds a request object. Params which serialize to `null` (such as `()`) are omitted, since
+/// JSON-RPC only allows arrays and objects.
+fn request_object<P>(method: &str, params: P, id: Option<u64>) -> RaxiosResult<Value>
+where
+    P: Serialize,
+{
+    let params = serde_json::to_value(params)
+        .map_err(|e| RaxiosError::SerializationError(SerializationError::Json(e)))?;
+
+    let mut request = json!({"jsonrpc": "2.0", "method": method});
+    match params {
+        Value::Null => {}
+        Value::Array(_) | Value::Object(_) => {
+            request["params"] = params;
+        }
+        _ => {
+            return Err(RaxiosError::SerializationError(
+                SerializationError::Unknown(format!(
+                    "JSON-RPC params of `{method}` must serialize to an array or object"
+                )),
+            ))
+        }
+    }
+    if let Some(id) = id {
+        request["id"] = id.into();
+    }
+    Ok(request)
+}
+
+/// A JSON-RPC 2.0 client which sends every request as an HTTP POST to one endpoint.
+///
+/// Created with `Raxios::json_rpc`. Cloning is cheap, and clones share the sequence of request
+/// ids.
+///
+/// # Example
+/// ```rust
+/// use httpmock::prelude::*;
+/// use raxios::Raxios;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let server = MockServer::start();
+///
+///     server.mock(|when, then| {
+///         when.path("/rpc").method(POST).json_body(serde_json::json!({
+///             "jsonrpc": "2.0",
+///             "method": "add",
+///             "params": [2, 3],
+///             "id": 1
+///         }));
+///         then.status(200)
+///             .json_body(serde_json::json!({"jsonrpc": "2.0", "result": 5, "id": 1}));
+///     });
+///
+///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+///     let rpc = raxios.json_rpc("/rpc", None);
+///
+///     assert_eq!(5, rpc.call::<_, u32>("add", [2, 3]).await.unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonRpcClient {
+    raxios: Raxios,
+    endpoint: String,
+    options: RaxiosOptions,
+    next_id: Arc<AtomicU64>,
+}
+
+impl JsonRpcClient {
+    fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Posts `body`, returning the parsed response body, or `None` when the server sent none
+    async fn post(&self, body: &Value) -> RaxiosResult<Option<Value>> {
+        let response = match self
+            .raxios
+            .post::<Value, _>(&self.endpoint, Some(body), Some(self.options.clone()))
+            .await
+        {
+            Ok(response) => response,
+            // Some servers send an error object with a 4xx or 5xx status
+            Err(RaxiosError::NetworkError(err)) => {
+                let error = err
+                    .raw_body
+                    .as_ref()
+                    .and_then(|raw_body| serde_json::from_slice::<JsonRpcResponse>(raw_body).ok())
+                    .and_then(|response| response.error);
+                return Err(match error {
+                    Some(error) => RaxiosError::JsonRpc(error),
+                    None => RaxiosError::NetworkError(err),
+                });
+            }
+            Err(err) => return Err(err),
+        };
+
+        match response.raw_body {
+            Some(raw_body) if !raw_body.iter().all(u8::is_ascii_whitespace) => {
+                serde_json::from_slice(&raw_body)
+                    .map(Some)
+                    .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Calls `method` with `params` and deserializes its `result`. An error object in the response
+    /// is returned as `RaxiosError::JsonRpc`.
+    pub async fn call<P, R>(&self, method: &str, params: P) -> RaxiosResult<R>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let id = self.next_id();
+        let body = self
+            .post(&request_object(method, params, Some(id))?)
+            .await?
+            .ok_or_else(|| {
+                RaxiosError::DeserializationError(DeserializationError::Unknown(format!(
+                    "No response to JSON-RPC request {id}"
+                )))
+            })?;
+
+        let response = deserialize_json::<JsonRpcResponse>(body)?;
+        if let Some(error) = response.error {
+            return Err(RaxiosError::JsonRpc(error));
+        }
+        if response.id != id {
+            return Err(RaxiosError::DeserializationError(
+                DeserializationError::Unknown(format!(
+                    "Expected a response to JSON-RPC request {id}, got {}",
+                    response.id
+                )),
+            ));
+        }
+        deserialize_json(response.result)
+    }
+
+    /// Sends a notification, which has no id and gets no response
+    pub async fn notify<P>(&self, method: &str, params: P) -> RaxiosResult<()>
+    where
+        P: Serialize,
+    {
+        self.post(&request_object(method, params, None)?).await?;
+        Ok(())
+    }
+
+    /// Starts a batch of calls and notifications, which are sent together by `JsonRpcBatch::send`
+    pub fn batch(&self) -> JsonRpcBatch {
+        JsonRpcBatch {
+            client: self.clone(),
+            requests: Vec::new(),
+        }
+    }
+}
+
+/// Identifies a call within a `JsonRpcBatch`, to read its result from the `JsonRpcBatchResponse`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JsonRpcId(u64);
+
+/// A batch of JSON-RPC calls and notifications which are sent in a single HTTP request
+#[derive(Debug, Clone)]
+pub struct JsonRpcBatch {
+    client: JsonRpcClient,
+    requests: Vec<Value>,
+}
+
+impl JsonRpcBatch {
+    /// Adds a call of `method` to the batch
+    pub fn call<P>(&mut self, method: &str, params: P) -> RaxiosResult<JsonRpcId>
+    where
+        P: Serialize,
+    {
+        let id = self.client.next_id();
+        self.requests
+            .push(request_object(method, params, Some(id))?);
+        Ok(JsonRpcId(id))
+    }
+
+    /// Adds a notification of `method` to the batch
+    pub fn notify<P>(&mut self, method: &str, params: P) -> RaxiosResult<()>
+    where
+        P: Serialize,
+    {
+        self.requests.push(request_object(method, params, None)?);
+        Ok(())
+    }
+
+    /// Sends every call and notification, and correlates the responses with their calls by id,
+    /// whatever order the server sent them in.
+    ///
+    /// If the server rejects the whole batch with a single error object, it is returned as
+    /// `RaxiosError::JsonRpc`.
+    pub async fn send(self) -> RaxiosResult<JsonRpcBatchResponse> {
+        let mut responses = HashMap::new();
+        if self.requests.is_empty() {
+            return Ok(JsonRpcBatchResponse { responses });
+        }
+
+        let entries = match self.client.post(&Value::Array(self.requests)).await? {
+            None => Vec::new(),
+            Some(Value::Array(entries)) => entries,
+            Some(single) => {
+                let response = deserialize_json::<JsonRpcResponse>(single)?;
+                return Err(match response.error {
+                    Some(error) => RaxiosError::JsonRpc(error),
+                    None => RaxiosError::DeserializationError(DeserializationError::Unknown(
+                        String::from("Expected an array of JSON-RPC responses"),
+                    )),
+                });
+            }
+        };
+
+        for entry in entries {
+            let response = deserialize_json::<JsonRpcResponse>(entry)?;
+            match response.id.as_u64() {
+                Some(id) => {
+                    responses.insert(id, response.into_result());
+                }
+                // A response without an id means the server could not read the request at all
+                None => {
+                    if let Some(error) = response.error {
+                        return Err(RaxiosError::JsonRpc(error));
+                    }
+                }
+            }
+        }
+        Ok(JsonRpcBatchResponse { responses })
+    }
+}
+
+/// The responses to the calls of a `JsonRpcBatch`
+#[derive(Debug, Clone, Default)]
+pub struct JsonRpcBatchResponse {
+    responses: HashMap<u64, Result<Value, JsonRpcError>>,
+}
+
+impl JsonRpcBatchResponse {
+    /// Deserializes the result of the call `id`, or returns its error object as
+    /// `RaxiosError::JsonRpc`
+    pub fn result<R>(&self, id: JsonRpcId) -> RaxiosResult<R>
+    where
+        R: DeserializeOwned,
+    {
+        match self.responses.get(&id.0) {
+            Some(Ok(result)) => R::deserialize(result)
+                .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e))),
+            Some(Err(error)) => Err(RaxiosError::JsonRpc(error.clone())),
+            None => Err(RaxiosError::DeserializationError(
+                DeserializationError::Unknown(format!("No response to JSON-RPC request {}", id.0)),
+            )),
+        }
+    }
+
+    /// The number of responses received
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
+
+impl Raxios {
+    /// Creates a JSON-RPC 2.0 client which posts to `endpoint`. Request bodies are always sent as
+    /// `application/json`.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `options` - The `RaxiosOptions` for every request. Allows setting of headers and/or query params
+    pub fn json_rpc(&self, endpoint: &str, options: Option<RaxiosOptions>) -> JsonRpcClient {
+        let mut options = options.unwrap_or_default();
+        options.content_type = Some(ContentType::Json);
+        options.accept = Some(ContentType::Json);
+        options.deserialize_body = false;
+
+        JsonRpcClient {
+            raxios: self.clone(),
+            endpoint: endpoint.to_owned(),
+            options,
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    use super::JsonRpcError;
+    use crate::{ErrorKind, Raxios, RaxiosError};
+
+    #[tokio::test]
+    async fn test_call_maps_error_objects() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/rpc")
+                .json_body(json!({"jsonrpc": "2.0", "method": "missing", "id": 1}));
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "error": {"code": -32601, "message": "Method not found", "data": "missing"},
+                "id": 1
+            }));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let err = raxios
+            .json_rpc("/rpc", None)
+            .call::<_, ()>("missing", ())
+            .await
+            .unwrap_err();
+
+        assert_eq!(ErrorKind::JsonRpc, err.kind());
+        assert!(matches!(
+            err,
+            RaxiosError::JsonRpc(JsonRpcError { code: -32601, ref data, .. })
+                if *data == Some(json!("missing"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_call_rejects_scalar_params() {
+        let raxios = Raxios::new("http://localhost", None).unwrap();
+        let err = raxios
+            .json_rpc("/rpc", None)
+            .call::<_, ()>("echo", 1)
+            .await
+            .unwrap_err();
+        assert_eq!(ErrorKind::Serialization, err.kind());
+    }
+
+    #[tokio::test]
+    async fn test_notify() {
+        let server = MockServer::start();
+        let notification = server.mock(|when, then| {
+            when.path("/rpc")
+                .json_body(json!({"jsonrpc": "2.0", "method": "log", "params": {"level": "info"}}));
+            then.status(204);
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        raxios
+            .json_rpc("/rpc", None)
+            .notify("log", json!({"level": "info"}))
+            .await
+            .unwrap();
+        notification.assert();
+    }
+
+    #[tokio::test]
+    async fn test_batch_correlates_ids() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/rpc").json_body(json!([
+                {"jsonrpc": "2.0", "method": "add", "params": [1, 2], "id": 1},
+                {"jsonrpc": "2.0", "method": "ping"},
+                {"jsonrpc": "2.0", "method": "divide", "params": [1, 0], "id": 2}
+            ]));
+            then.status(200).json_body(json!([
+                {"jsonrpc": "2.0", "error": {"code": -32000, "message": "Division by zero"}, "id": 2},
+                {"jsonrpc": "2.0", "result": 3, "id": 1}
+            ]));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let mut batch = raxios.json_rpc("/rpc", None).batch();
+        let add = batch.call("add", [1, 2]).unwrap();
+        batch.notify("ping", ()).unwrap();
+        let divide = batch.call("divide", [1, 0]).unwrap();
+        let responses = batch.send().await.unwrap();
+
+        assert_eq!(2, responses.len());
+        assert_eq!(3, responses.result::<u32>(add).unwrap());
+        assert!(matches!(
+            responses.result::<u32>(divide),
+            Err(RaxiosError::JsonRpc(JsonRpcError { code: -32000, .. }))
+        ));
+    }
+}
@@ -1,50 +1,141 @@
+// The original code favours explicit `return`s and spelled-out assertions over clippy's defaults
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::derivable_impls,
+    clippy::needless_question_mark,
+    clippy::needless_return,
+    clippy::redundant_static_lifetimes
+)]
+
+mod batch;
+mod codec;
+#[cfg(feature = "csv")]
+mod csv_rows;
+mod dedupe;
 mod error;
+mod graphql;
+mod headers;
+mod json_array;
+mod json_rpc;
+mod media_type;
+mod ndjson;
 mod network_error;
+mod pagination;
+mod path_params;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+mod query;
+mod raw;
 mod raxios_config;
 mod raxios_options;
 mod raxios_response;
+mod soap;
+mod sse;
+mod typed_headers;
+mod uri_template;
 mod utils;
 
 use anyhow::anyhow;
+use arc_swap::{ArcSwap, Guard};
+pub use batch::BatchOptions;
 use bytes::Bytes;
-pub use error::{DeserializationError, RaxiosError, RaxiosResult, SerializationError};
+pub use codec::{Codec, CodecRegistry, DecodeTarget, JsonCodec, UrlEncodedCodec, XmlCodec};
+use dedupe::InFlight;
+pub use erased_serde;
+pub use error::{DeserializationError, ErrorKind, RaxiosError, RaxiosResult, SerializationError};
+use futures_util::Stream;
+pub use graphql::{
+    GraphQLError, GraphQLErrors, GraphQLLocation, GraphQLPathSegment, GraphQLRequest,
+};
+pub use headers::RaxiosHeaders;
+pub use json_rpc::{JsonRpcBatch, JsonRpcBatchResponse, JsonRpcClient, JsonRpcError, JsonRpcId};
+pub use media_type::MediaType;
 pub use network_error::NetworkError;
-pub use raxios_config::RaxiosConfig;
-pub use raxios_options::{ContentType, RaxiosOptions};
+pub use pagination::{PageStrategy, Pagination};
+pub use path_params::PathParams;
+pub use query::{ArrayFormat, QueryParams};
+pub use raw::RawError;
+pub use raxios_config::{RaxiosAuth, RaxiosConfig, RaxiosOverrides};
+#[cfg(feature = "csv")]
+pub use raxios_options::CsvOptions;
+pub use raxios_options::{ContentType, RaxiosOptions, SseOptions};
 pub use raxios_response::RaxiosResponse;
 pub use reqwest;
 pub use reqwest::StatusCode;
-use reqwest::{header::HeaderMap, Client, ClientBuilder, RequestBuilder, Response, Url};
+use reqwest::{
+    header::{
+        HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE,
+        USER_AGENT as USER_AGENT_HEADER,
+    },
+    Client, ClientBuilder, RequestBuilder, Response, Url,
+};
 use serde::{Deserialize, Serialize};
+pub use soap::{SoapFault, SoapRequest, SoapVersion};
+pub use sse::SseEvent;
 use std::str::FromStr;
-use std::{collections::HashMap, time::Duration};
-use utils::{map_to_reqwest_headers, reqwest_headers_to_map};
-
-pub type RaxiosHeaders = HashMap<String, String>;
+use std::{net::SocketAddr, pin::Pin, sync::Arc, time::Duration};
+pub use typed_headers::{
+    CacheControl, ContentDisposition, ContentLength, EntityTag, LastModified, Link, Links,
+    Location, TypedHeader, TypedHeaders,
+};
+pub use uri_template::{UriTemplate, UriVariables};
+use utils::{map_to_reqwest_headers, reqwest_headers_to_map, resolve_url};
+
+/// A stream of items which are decoded from a response body as it arrives
+pub type RaxiosStream<T> = Pin<Box<dyn Stream<Item = RaxiosResult<T>> + Send>>;
 const USER_AGENT: &'static str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// The settings of a `Raxios` instance, which are swapped out as a whole when they change
 #[derive(Debug)]
+struct Defaults {
+    config: RaxiosConfig,
+    /// The headers of `config`, sent with every request
+    headers: HeaderMap,
+    base_url: String,
+}
+
+impl Defaults {
+    fn new(base_url: &str, mut config: RaxiosConfig) -> RaxiosResult<Self> {
+        let mut headers = config.headers.clone().unwrap_or_default();
+        Raxios::insert_default_headers(&mut headers, Some(&config));
+        let default_headers = map_to_reqwest_headers(&headers)?;
+        config.headers = Some(headers);
+
+        Ok(Self {
+            config,
+            headers: default_headers,
+            base_url: base_url.to_string(),
+        })
+    }
+}
+
+/// An HTTP client. Cloning is cheap: clones share the connection pool and the defaults of the
+/// original, so a change made with `set_default_headers`, `set_base_url` or `set_auth` through
+/// any clone applies to all of them.
+#[derive(Debug, Clone)]
 pub struct Raxios {
     client: Client,
-    config: Option<RaxiosConfig>,
-    base_url: String,
+    defaults: Arc<ArcSwap<Defaults>>,
+    /// The GET requests in flight which identical requests may share
+    in_flight: Arc<InFlight>,
 }
 
 impl Default for Raxios {
     fn default() -> Self {
-        let mut headers: RaxiosHeaders = HashMap::new();
+        let mut headers = RaxiosHeaders::new();
         Self::insert_default_headers(&mut headers, Default::default());
 
         Self {
-            client: ClientBuilder::default()
-                .default_headers(map_to_reqwest_headers(&headers).unwrap())
-                .build()
-                .unwrap(),
-            config: Some(RaxiosConfig {
-                headers: Some(headers),
-                ..Default::default()
-            }),
-            base_url: Default::default(),
+            client: Client::new(),
+            defaults: Arc::new(ArcSwap::from_pointee(Defaults {
+                headers: map_to_reqwest_headers(&headers).unwrap(),
+                config: RaxiosConfig {
+                    headers: Some(headers),
+                    ..Default::default()
+                },
+                base_url: Default::default(),
+            })),
+            in_flight: Default::default(),
         }
     }
 }
@@ -60,109 +151,188 @@ impl Raxios {
     ///
     /// ```
     pub fn new(base_url: &str, options: Option<RaxiosConfig>) -> RaxiosResult<Self> {
-        let mut options = options.unwrap_or_default();
-        let mut headers = options
-            .headers
-            .as_ref()
-            .map(|r| r.clone())
-            .unwrap_or_default();
+        let client = ClientBuilder::default()
+            .build()
+            .map_err(|e| RaxiosError::Unknown(anyhow!(e)))?;
 
-        Self::insert_default_headers(&mut headers, Some(&options));
-        options.headers = Some(headers);
-
-        let default_headers: HeaderMap;
-        let mut client = ClientBuilder::default();
-        if let Some(headers) = &options.headers {
-            default_headers = map_to_reqwest_headers(&headers)?;
-            client = client.default_headers(default_headers);
-        }
-        if let Some(timeout) = &options.timeout_ms {
-            client = client.timeout(Duration::from_millis(timeout.to_owned()))
-        }
+        Self::with_client(client, base_url, options.unwrap_or_default())
+    }
 
+    /// Creates an instance which sends its requests through `client`, sharing its connection pool
+    fn with_client(client: Client, base_url: &str, config: RaxiosConfig) -> RaxiosResult<Self> {
         Ok(Self {
-            base_url: base_url.to_string(),
-            config: Some(options),
-            client: client
-                .build()
-                .map_err(|e| RaxiosError::Unknown(anyhow!(e)))?,
-            ..Default::default()
+            client,
+            defaults: Arc::new(ArcSwap::from_pointee(Defaults::new(base_url, config)?)),
+            in_flight: Default::default(),
         })
     }
 
+    /// A snapshot of the current defaults of this instance
+    fn defaults(&self) -> Arc<Defaults> {
+        self.defaults.load_full()
+    }
+
+    /// Atomically replaces the defaults with the result of `update`. If another thread changes
+    /// the defaults in the meantime, `update` is retried with the new defaults so no change is lost.
+    fn update_defaults<F>(&self, update: F) -> RaxiosResult<()>
+    where
+        F: Fn(&Defaults) -> RaxiosResult<Defaults>,
+    {
+        let mut current = self.defaults.load_full();
+        loop {
+            let updated = Arc::new(update(&current)?);
+            let previous = self.defaults.compare_and_swap(&current, updated);
+            if Arc::ptr_eq(&previous, &current) {
+                return Ok(());
+            }
+            current = Guard::into_inner(previous);
+        }
+    }
+
+    /// Derives a new instance from this one, like `axios.create`. The new instance shares the
+    /// connection pool of this one, and inherits its config except for what `overrides` changes.
+    /// Later changes to the defaults of this instance do not affect the derived instance.
+    ///
+    /// Settings are layered with the following precedence, from lowest to highest: library
+    /// defaults, the config of this instance, `overrides`, and the `RaxiosOptions` of each request.
+    ///
+    /// # Example
+    /// ```rust
+    /// use raxios::{map_string, Raxios, RaxiosAuth, RaxiosConfig, RaxiosOverrides};
+    ///
+    /// let parent = Raxios::new(
+    ///     "http://localhost",
+    ///     Some(RaxiosConfig {
+    ///         headers: Some(map_string! {"x-tenant" => "acme"}.into()),
+    ///         ..Default::default()
+    ///     }),
+    /// )
+    /// .unwrap();
+    ///
+    /// let admin = parent
+    ///     .create(RaxiosOverrides {
+    ///         base_url: Some(String::from("http://localhost/admin")),
+    ///         auth: Some(RaxiosAuth::Bearer(String::from("token"))),
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn create(&self, overrides: RaxiosOverrides) -> RaxiosResult<Self> {
+        let defaults = self.defaults();
+        let parent = defaults.config.clone();
+        let child_headers = overrides.headers.unwrap_or_default();
+        let auth = match overrides.auth {
+            Some(auth) => Some(auth),
+            // An explicit authorization header replaces the credentials of the parent
+            None if child_headers.contains_key(AUTHORIZATION.as_str()) => None,
+            None => parent.auth,
+        };
+        let mut headers = parent.headers.unwrap_or_default();
+        headers.merge(&child_headers);
+
+        let config = RaxiosConfig {
+            timeout_ms: overrides.timeout_ms.or(parent.timeout_ms),
+            headers: Some(headers),
+            accept: overrides.accept.unwrap_or(parent.accept),
+            content_type: overrides.content_type.unwrap_or(parent.content_type),
+            auth,
+            ..parent
+        };
+
+        Self::with_client(
+            self.client.clone(),
+            overrides.base_url.as_deref().unwrap_or(&defaults.base_url),
+            config,
+        )
+    }
+
     fn insert_default_headers(headers: &mut RaxiosHeaders, config: Option<&RaxiosConfig>) {
-        headers.insert("user-agent".to_string(), USER_AGENT.to_string());
+        headers.insert(USER_AGENT_HEADER, USER_AGENT);
         if let Some(config) = config {
-            headers.insert(
-                reqwest::header::CONTENT_TYPE.to_string(),
-                config.content_type.clone().to_string(),
-            );
-            headers.insert(
-                reqwest::header::ACCEPT.to_string(),
-                config.accept.clone().to_string(),
-            );
+            headers.insert(CONTENT_TYPE, config.content_type.to_string());
+            headers.insert(ACCEPT, config.accept.to_string());
+            if let Some(auth) = &config.auth {
+                headers.insert(AUTHORIZATION, auth.header_value());
+            }
         }
     }
 
-    /// Sets the default headers for this instance of Raxios.
+    /// Sets the default headers for this instance of Raxios and all of its clones.
     ///
     /// # Example
     /// ```rust
     /// use raxios::{Raxios, map_string};
     ///
-    /// let mut client = Raxios::new("http://localhost", None).unwrap();
-    /// let set_header_result = client.set_default_headers(Some(map_string!{ header1 : "header 1 value" }));
+    /// let client = Raxios::new("http://localhost", None).unwrap();
+    /// let set_header_result = client.set_default_headers(Some(map_string!{ header1 : "header 1 value" }.into()));
     /// assert_ne!(true, set_header_result.is_err());
     ///
     /// ```
-    pub fn set_default_headers(&mut self, headers: Option<RaxiosHeaders>) -> RaxiosResult<()> {
-        let mut headers = headers.unwrap_or_default();
-
-        Self::insert_default_headers(&mut headers, self.config.as_ref());
-
-        let opts: RaxiosConfig = RaxiosConfig {
-            headers: Some(headers),
-            ..self.config.clone().unwrap_or(Default::default())
-        };
+    pub fn set_default_headers(&self, headers: Option<RaxiosHeaders>) -> RaxiosResult<()> {
+        let headers = headers.unwrap_or_default();
+        self.update_defaults(|defaults| {
+            let config = RaxiosConfig {
+                headers: Some(headers.clone()),
+                ..defaults.config.clone()
+            };
+            Defaults::new(&defaults.base_url, config)
+        })
+    }
 
-        let new_raxios = Self::new(&self.base_url, Some(opts))?;
-        self.client = new_raxios.client;
-        self.config = new_raxios.config;
+    /// Sets the base url of this instance of Raxios and all of its clones
+    pub fn set_base_url(&self, base_url: &str) {
+        self.defaults.rcu(|defaults| Defaults {
+            config: defaults.config.clone(),
+            headers: defaults.headers.clone(),
+            base_url: base_url.to_string(),
+        });
+    }
 
-        Ok(())
+    /// Sets the credentials sent in the `authorization` header by this instance of Raxios and all
+    /// of its clones. `None` stops sending the `authorization` header.
+    pub fn set_auth(&self, auth: Option<RaxiosAuth>) -> RaxiosResult<()> {
+        self.update_defaults(|defaults| {
+            let mut config = defaults.config.clone();
+            if let Some(headers) = config.headers.as_mut() {
+                headers.remove(AUTHORIZATION.as_str());
+            }
+            config.auth = auth.clone();
+            Defaults::new(&defaults.base_url, config)
+        })
     }
 
     fn build_url(&self, endpoint: &str, options: Option<&RaxiosOptions>) -> RaxiosResult<Url> {
-        let mut built_string = String::new();
-        built_string += &self.base_url;
+        let expanded_template = match options.and_then(|options| options.uri_variables.as_ref()) {
+            Some(variables) => Some(endpoint.parse::<UriTemplate>()?.expand(variables)),
+            None => None,
+        };
+        let endpoint = path_params::expand_path(
+            expanded_template.as_deref().unwrap_or(endpoint),
+            options.and_then(|options| options.path_params.as_ref()),
+        )?;
 
-        if built_string.chars().nth(built_string.chars().count() - 1) != Some('/')
-            && endpoint.chars().nth(0) != Some('/')
-        {
-            built_string += "/";
-        }
+        let mut url = resolve_url(&self.defaults().base_url, &endpoint)?;
 
-        built_string += endpoint;
         if let Some(options) = options {
-            if let Some(params) = &options.params {
-                let mut added_param = false;
-                for (index, (key, value)) in params.iter().enumerate() {
-                    if !added_param {
-                        built_string += "?";
-                        added_param = true;
-                    }
-                    built_string += &format!("{key}={value}");
-                    if index < params.len() - 1 {
-                        built_string += "&";
-                    }
-                }
+            let params = options.params.clone().map(QueryParams::from);
+            let encoded = params
+                .iter()
+                .chain(options.query.iter())
+                .map(QueryParams::encode)
+                .filter(|encoded| !encoded.is_empty());
+            let query = url
+                .query()
+                .filter(|query| !query.is_empty())
+                .map(str::to_owned)
+                .into_iter()
+                .chain(encoded)
+                .collect::<Vec<_>>()
+                .join("&");
+            if !query.is_empty() {
+                url.set_query(Some(&query));
             }
         }
 
-        let url: Url = built_string
-            .parse()
-            .map_err(|_| RaxiosError::InvalidUrl(built_string))?;
-
         Ok(url)
     }
 
@@ -174,31 +344,64 @@ impl Raxios {
     where
         U: Serialize,
     {
-        let mut content_type: ContentType = Default::default();
-
-        if let Some(opts) = options {
-            if let Some(ref c_type) = opts.content_type {
-                content_type = c_type.clone();
-            } else {
-                if let Some(ref config) = self.config {
-                    content_type = config.content_type.clone();
-                }
-            }
-        } else {
-            if let Some(ref config) = self.config {
-                content_type = config.content_type.clone();
+        let defaults = self.defaults();
+        let content_type = options
+            .and_then(|opts| opts.content_type.clone())
+            .unwrap_or_else(|| defaults.config.content_type.clone());
+
+        if let Ok(media_type) = MediaType::from_str(&content_type.to_string()) {
+            if let Some(codec) = defaults.config.codecs.find(&media_type) {
+                let body = codec
+                    .encode(&data)
+                    .map_err(RaxiosError::SerializationError)?;
+                return Ok((body, content_type));
             }
         }
 
         let data_to_return = match content_type {
-            ContentType::Json => serde_json::to_vec(&data)
-                .map_err(|e| RaxiosError::SerializationError(SerializationError::Json(e)))?,
-            ContentType::TextXml | ContentType::ApplicationXml => serde_xml_rs::to_string(&data)
-                .map_err(|e| RaxiosError::SerializationError(SerializationError::Xml(e)))?
+            ContentType::Text | ContentType::OctetStream => raw::to_vec(&data)
+                .map_err(|e| RaxiosError::SerializationError(SerializationError::Raw(e)))?,
+            #[cfg(feature = "csv")]
+            ContentType::Csv => csv_rows::to_csv(&data, &self.csv_options(options))?,
+            ContentType::NdJson => match serde_json::to_value(&data)
+                .map_err(|e| RaxiosError::SerializationError(SerializationError::Json(e)))?
+            {
+                serde_json::Value::Array(items) => ndjson::to_ndjson(items)?,
+                item => ndjson::to_ndjson([item])?,
+            },
+            #[cfg(feature = "msgpack")]
+            ContentType::MessagePack => rmp_serde::to_vec_named(&data)
+                .map_err(|e| RaxiosError::SerializationError(SerializationError::MessagePack(e)))?,
+            #[cfg(feature = "cbor")]
+            ContentType::Cbor => {
+                let mut buffer = Vec::new();
+                ciborium::ser::into_writer(&data, &mut buffer)
+                    .map_err(|e| RaxiosError::SerializationError(SerializationError::Cbor(e)))?;
+                buffer
+            }
+            #[cfg(feature = "yaml")]
+            ContentType::Yaml => serde_yaml::to_string(&data)
+                .map_err(|e| RaxiosError::SerializationError(SerializationError::Yaml(e)))?
                 .into_bytes(),
-            ContentType::UrlEncoded => serde_urlencoded::to_string(&data)
-                .map_err(|e| RaxiosError::SerializationError(SerializationError::UrlEncoded(e)))?
+            #[cfg(feature = "toml")]
+            ContentType::Toml => toml::to_string(&data)
+                .map_err(|e| RaxiosError::SerializationError(SerializationError::Toml(e)))?
                 .into_bytes(),
+            #[cfg(feature = "protobuf")]
+            ContentType::Protobuf => {
+                return Err(RaxiosError::SerializationError(
+                    SerializationError::UnsupportedContentType(content_type.to_string()),
+                ))
+            }
+            ContentType::Json
+            | ContentType::TextXml
+            | ContentType::ApplicationXml
+            | ContentType::UrlEncoded
+            | ContentType::Other(_) => {
+                return Err(RaxiosError::SerializationError(
+                    SerializationError::UnsupportedContentType(content_type.to_string()),
+                ))
+            }
         };
 
         return Ok((data_to_return, content_type));
@@ -213,96 +416,222 @@ impl Raxios {
     where
         U: Serialize,
     {
+        let body = data.map(|data| self.make_body(data, options)).transpose()?;
+        self.build_raw_request(body, options, original_builder)
+    }
+
+    /// Like `build_request`, but with a body that has already been encoded
+    fn build_raw_request<B>(
+        &self,
+        body: Option<(B, ContentType)>,
+        options: Option<&RaxiosOptions>,
+        original_builder: RequestBuilder,
+    ) -> RaxiosResult<RequestBuilder>
+    where
+        B: Into<reqwest::Body>,
+    {
+        let header_value = |name: HeaderName, value: String| {
+            HeaderValue::from_str(&value)
+                .map_err(|_| RaxiosError::HeaderParseError(name.to_string(), value))
+        };
+
+        let defaults = self.defaults();
         let mut builder = original_builder;
+        let mut headers = defaults.headers.clone();
         if let Some(options) = options {
-            if let Some(headers) = &options.headers {
-                builder = builder.headers(map_to_reqwest_headers(headers)?);
+            if let Some(option_headers) = &options.headers {
+                headers.extend(map_to_reqwest_headers(option_headers)?);
             }
         };
-        if let Some(body) = data {
-            let (body, content_type) = self.make_body(body, options)?;
+        if let Some((body, content_type)) = body {
             builder = builder.body(body);
-            builder = builder.header(reqwest::header::CONTENT_TYPE, format!("{content_type}"));
+            headers.insert(
+                CONTENT_TYPE,
+                header_value(CONTENT_TYPE, content_type.to_string())?,
+            );
         }
         if let Some(opts) = options {
             if let Some(ref accept) = opts.accept {
-                builder = builder.header(reqwest::header::ACCEPT.to_string(), accept.to_string());
+                headers.insert(ACCEPT, header_value(ACCEPT, accept.to_string())?);
             }
         }
+        builder = builder.headers(headers);
+
+        let timeout_ms = options
+            .and_then(|options| options.timeout_ms)
+            .or(defaults.config.timeout_ms);
+        if let Some(timeout_ms) = timeout_ms {
+            builder = builder.timeout(Duration::from_millis(timeout_ms));
+        }
 
         return Ok(builder);
     }
 
+    #[cfg_attr(not(feature = "csv"), allow(unused_variables))]
     fn deserialize_response<T>(
         &self,
         raw_body: &Bytes,
         content_type: ContentType,
+        media_type: Option<&MediaType>,
+        options: Option<&RaxiosOptions>,
     ) -> RaxiosResult<T>
     where
         T: for<'de> Deserialize<'de>,
     {
-        return match content_type {
-            ContentType::Json => Ok(serde_json::from_slice::<T>(raw_body)
-                .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))?),
-            ContentType::TextXml | ContentType::ApplicationXml => {
+        let charset = media_type.and_then(|media_type| media_type.charset());
+        let codec_media_type = media_type
+            .cloned()
+            .or_else(|| MediaType::from_str(&content_type.to_string()).ok());
+        let defaults = self.defaults();
+        if let Some(codec) =
+            codec_media_type.and_then(|media_type| defaults.config.codecs.find(&media_type))
+        {
+            let mut value = None;
+            let mut sink = |deserializer: &mut dyn erased_serde::Deserializer<'_>| {
+                value = Some(erased_serde::deserialize::<T>(deserializer)?);
+                Ok(())
+            };
+            codec
+                .decode(raw_body, charset, &mut DecodeTarget::new(&mut sink))
+                .map_err(RaxiosError::DeserializationError)?;
+            return value.ok_or_else(|| {
+                RaxiosError::DeserializationError(DeserializationError::Unknown(format!(
+                    "No value was decoded from a response with content-type {content_type}"
+                )))
+            });
+        }
+
+        match content_type {
+            ContentType::Text => T::deserialize(raw::RawDeserializer::text(raw_body, charset))
+                .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Raw(e))),
+            ContentType::OctetStream => T::deserialize(raw::RawDeserializer::bytes(raw_body))
+                .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Raw(e))),
+            ContentType::NdJson => ndjson::from_ndjson(raw_body),
+            #[cfg(feature = "csv")]
+            ContentType::Csv => csv_rows::from_csv(raw_body, charset, &self.csv_options(options)),
+            #[cfg(feature = "msgpack")]
+            ContentType::MessagePack => rmp_serde::from_slice::<T>(raw_body).map_err(|e| {
+                RaxiosError::DeserializationError(DeserializationError::MessagePack(e))
+            }),
+            #[cfg(feature = "cbor")]
+            ContentType::Cbor => ciborium::de::from_reader::<T, _>(raw_body.as_ref())
+                .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Cbor(e))),
+            #[cfg(feature = "yaml")]
+            ContentType::Yaml => serde_yaml::from_slice::<T>(raw_body)
+                .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Yaml(e))),
+            #[cfg(feature = "toml")]
+            ContentType::Toml => {
                 let body_string = String::from_utf8(raw_body.to_vec()).map_err(|_| {
                     RaxiosError::DeserializationError(DeserializationError::Unknown(String::from(
                         "Response body does not contain valid Utf8",
                     )))
                 })?;
-                Ok(serde_xml_rs::from_str::<T>(&body_string)
-                    .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Xml(e)))?)
-            }
-            ContentType::UrlEncoded => {
-                Ok(serde_urlencoded::from_bytes::<T>(raw_body).map_err(|e| {
-                    RaxiosError::DeserializationError(DeserializationError::UrlEncoded(e))
-                })?)
+                toml::from_str::<T>(&body_string)
+                    .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Toml(e)))
             }
-        };
+            #[cfg(feature = "protobuf")]
+            ContentType::Protobuf => Err(RaxiosError::DeserializationError(
+                DeserializationError::UnsupportedContentType(content_type.to_string()),
+            )),
+            ContentType::Json
+            | ContentType::TextXml
+            | ContentType::ApplicationXml
+            | ContentType::UrlEncoded
+            | ContentType::Other(_) => Err(RaxiosError::DeserializationError(
+                DeserializationError::UnsupportedContentType(content_type.to_string()),
+            )),
+        }
     }
 
     async fn check_response_and_return_err(&self, response: Response) -> RaxiosResult<Response> {
-        if response.status().is_client_error() || response.status().is_server_error() {
-            return Err(RaxiosError::NetworkError(NetworkError::new(response).await));
-        }
-        Ok(response)
+        NetworkError::check(response)
+            .await
+            .map_err(RaxiosError::NetworkError)
     }
 
     async fn response_to_raxios_response<T>(
         &self,
         response: Response,
-        deserialize_body: bool,
+        options: &RaxiosOptions,
     ) -> RaxiosResult<RaxiosResponse<T>>
     where
         T: for<'de> Deserialize<'de>,
     {
-        let response = self.check_response_and_return_err(response).await?;
-        let remote_content_type = response
-            .headers()
-            .get(reqwest::header::CONTENT_TYPE)
-            .map(|c_type| {
-                c_type
-                    .to_str()
-                    .ok()
-                    .map(|s| s.to_owned())
-                    .unwrap_or_default()
-            })
-            .map(|string| ContentType::from_str(&string).ok().unwrap_or_default());
+        self.response_to_raxios_response_with(
+            response,
+            options.deserialize_body,
+            |raw_body, content_type, media_type| {
+                self.deserialize_response(raw_body, content_type, media_type, Some(options))
+            },
+        )
+        .await
+    }
 
+    /// Like `response_to_raxios_response`, but decodes the body with `decode` instead of serde.
+    /// `decode` receives the raw body, the response content-type and its parsed media type.
+    async fn response_to_raxios_response_with<T, F>(
+        &self,
+        response: Response,
+        deserialize_body: bool,
+        decode: F,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        F: FnOnce(&Bytes, ContentType, Option<&MediaType>) -> RaxiosResult<T>,
+    {
+        let response = self.check_response_and_return_err(response).await?;
         let headers = response.headers().clone();
         let remote_address = response.remote_addr();
         let status = response.status();
-
         let raw_body = response.bytes().await.ok();
+
+        Self::decode_response_with(
+            status,
+            &headers,
+            remote_address,
+            raw_body,
+            deserialize_body,
+            decode,
+        )
+    }
+
+    /// Builds a `RaxiosResponse` from the parts of a response which has already been read,
+    /// decoding the body with `decode` like `response_to_raxios_response_with`
+    fn decode_response_with<T, F>(
+        status: StatusCode,
+        headers: &HeaderMap,
+        remote_address: Option<SocketAddr>,
+        raw_body: Option<Bytes>,
+        deserialize_body: bool,
+        decode: F,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        F: FnOnce(&Bytes, ContentType, Option<&MediaType>) -> RaxiosResult<T>,
+    {
+        let remote_content_type = headers.get(reqwest::header::CONTENT_TYPE).map(|c_type| {
+            c_type
+                .to_str()
+                .ok()
+                .map(|s| s.to_owned())
+                .unwrap_or_default()
+        });
+        let remote_media_type = remote_content_type
+            .as_ref()
+            .and_then(|string| MediaType::from_str(string).ok());
+
         let mut body: Option<T> = None;
 
         if let Some(raw_body) = &raw_body {
             if deserialize_body {
-                if let Some(response_content_type) = remote_content_type {
-                    body = Some(self.deserialize_response::<T>(raw_body, response_content_type)?);
-                } else {
-                    body = Some(self.deserialize_response::<T>(raw_body, ContentType::Json)?);
-                }
+                let response_content_type = match (&remote_media_type, remote_content_type) {
+                    (Some(media_type), _) => ContentType::from(media_type),
+                    (None, Some(string)) => ContentType::Other(string),
+                    (None, None) => ContentType::Json,
+                };
+                body = Some(decode(
+                    raw_body,
+                    response_content_type,
+                    remote_media_type.as_ref(),
+                )?);
             }
         }
 
@@ -310,7 +639,7 @@ impl Raxios {
             body,
             raw_body,
             status,
-            response_headers: reqwest_headers_to_map(&headers)?,
+            response_headers: reqwest_headers_to_map(headers),
             remote_address,
         });
     }
@@ -382,9 +711,7 @@ impl Raxios {
             .await
             .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
 
-        return Ok(self
-            .response_to_raxios_response(response, options.deserialize_body)
-            .await?);
+        return Ok(self.response_to_raxios_response(response, &options).await?);
     }
 
     /// Sends an HTTP GET request to the configured remote server
@@ -433,19 +760,20 @@ impl Raxios {
         T: for<'de> Deserialize<'de>,
     {
         let options = options.unwrap_or_default();
-        let response = self
-            .build_request::<()>(
-                None,
-                Some(&options),
-                self.client.get(self.build_url(endpoint, Some(&options))?),
-            )?
+        let builder = self.build_request::<()>(
+            None,
+            Some(&options),
+            self.client.get(self.build_url(endpoint, Some(&options))?),
+        )?;
+        if self.deduplicates(&options) {
+            return self.send_deduplicated(builder, &options).await;
+        }
+        let response = builder
             .send()
             .await
             .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
 
-        return Ok(self
-            .response_to_raxios_response(response, options.deserialize_body)
-            .await?);
+        return Ok(self.response_to_raxios_response(response, &options).await?);
     }
 
     /// Sends an HTTP DELETE request to the configured remote server
@@ -500,9 +828,7 @@ impl Raxios {
             .await
             .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
 
-        return Ok(self
-            .response_to_raxios_response(response, options.deserialize_body)
-            .await?);
+        return Ok(self.response_to_raxios_response(response, &options).await?);
     }
 
     /// Sends an HTTP PUT request to the configured remote server
@@ -556,9 +882,7 @@ impl Raxios {
             .await
             .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
 
-        return Ok(self
-            .response_to_raxios_response(response, options.deserialize_body)
-            .await?);
+        return Ok(self.response_to_raxios_response(response, &options).await?);
     }
 
     /// Sends an HTTP PATCH request to the configured remote server
@@ -612,9 +936,7 @@ impl Raxios {
             .await
             .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
 
-        return Ok(self
-            .response_to_raxios_response(response, options.deserialize_body)
-            .await?);
+        return Ok(self.response_to_raxios_response(response, &options).await?);
     }
 }
 
@@ -625,7 +947,7 @@ mod raxios_tests {
     use crate::{
         map_string,
         raxios_options::{ContentType, RaxiosOptions},
-        Raxios, RaxiosConfig, USER_AGENT,
+        DeserializationError, Raxios, RaxiosConfig, RaxiosError, USER_AGENT,
     };
     use httpmock::prelude::*;
     use serde::{Deserialize, Serialize};
@@ -647,9 +969,10 @@ mod raxios_tests {
 
         assert_eq!(
             raxios
+                .defaults()
                 .config
-                .unwrap()
                 .headers
+                .clone()
                 .unwrap()
                 .get("user-agent")
                 .unwrap(),
@@ -676,6 +999,24 @@ mod raxios_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_url_absolute_endpoint() -> anyhow::Result<()> {
+        let raxios = Raxios::new("http://localhost/api/v2/", None)?;
+        let built_url = raxios.build_url("https://other.host/x", None)?;
+        assert_eq!("https://other.host/x", built_url.as_str());
+
+        let built_url = raxios.build_url("/v1/signup", None)?;
+        assert_eq!("http://localhost/api/v2/v1/signup", built_url.as_str());
+
+        let built_url = Raxios::default().build_url("http://localhost/x", None)?;
+        assert_eq!("http://localhost/x", built_url.as_str());
+        assert!(matches!(
+            Raxios::default().build_url("/x", None),
+            Err(RaxiosError::InvalidUrl(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_build_url_with_params() -> anyhow::Result<()> {
         let raxios = Raxios::new("http://localhost", None).unwrap();
@@ -694,81 +1035,293 @@ mod raxios_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_url_encodes_and_merges_params() -> anyhow::Result<()> {
+        let raxios = Raxios::new("http://localhost", None).unwrap();
+        let built_url = raxios.build_url(
+            "/v1/search?sort=asc#results",
+            Some(&RaxiosOptions {
+                params: Some(map_string! {b : "x y", a : "1&c=2"}),
+                query: Some([("id", 1), ("id", 2)].into_iter().collect()),
+                ..Default::default()
+            }),
+        )?;
+
+        assert_eq!(
+            "http://localhost/v1/search?sort=asc&a=1%26c%3D2&b=x%20y&id=1&id=2#results",
+            built_url.as_str()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_url_with_path_params() -> anyhow::Result<()> {
+        let raxios = Raxios::new("http://localhost", None).unwrap();
+        let built_url = raxios.build_url(
+            "/users/{id}/orders/{order_id}",
+            Some(&RaxiosOptions {
+                path_params: Some([("id", "a/b"), ("order_id", "42")].into_iter().collect()),
+                ..Default::default()
+            }),
+        )?;
+        assert_eq!("http://localhost/users/a%2Fb/orders/42", built_url.as_str());
+
+        let missing = raxios.build_url(
+            "/users/{id}",
+            Some(&RaxiosOptions {
+                path_params: Some([("order_id", "42")].into_iter().collect()),
+                ..Default::default()
+            }),
+        );
+        assert!(matches!(missing, Err(RaxiosError::InvalidUrl(_))));
+
+        // Without path params, braces are sent as they are
+        let literal = raxios.build_url("/users/{id}", None)?;
+        assert_eq!("http://localhost/users/%7Bid%7D", literal.as_str());
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_url_with_uri_template() -> anyhow::Result<()> {
+        let raxios = Raxios::new("http://localhost", None).unwrap();
+        let built_url = raxios.build_url(
+            "/files{/path*}{?q,page}",
+            Some(&RaxiosOptions {
+                uri_variables: Some(crate::UriVariables::from_serialize(&serde_json::json!({
+                    "path": ["a", "b c"],
+                    "q": "x&y",
+                }))?),
+                params: Some(map_string! {sort : "asc"}),
+                ..Default::default()
+            }),
+        )?;
+
+        assert_eq!(
+            "http://localhost/files/a/b%20c?q=x%26y&sort=asc",
+            built_url.as_str()
+        );
+        Ok(())
+    }
+
     #[tokio::test]
-    async fn test_new_raxios_has_default_headers() -> anyhow::Result<()> {
+    async fn test_create_layers_config() -> anyhow::Result<()> {
         let server = MockServer::start();
-        let raxios = Raxios::new(&server.base_url(), None)?;
+        let parent = Raxios::new(
+            &server.base_url(),
+            Some(RaxiosConfig {
+                headers: Some(map_string! {"X-Tenant" => "acme", "x-parent" => "parent"}.into()),
+                auth: Some(crate::RaxiosAuth::Bearer(String::from("parent"))),
+                ..Default::default()
+            }),
+        )?;
+        let child = parent.create(crate::RaxiosOverrides {
+            base_url: Some(format!("{}/admin", server.base_url())),
+            headers: Some(map_string! {"x-tenant" => "child", "x-child" => "child"}.into()),
+            auth: Some(crate::RaxiosAuth::Bearer(String::from("child"))),
+            accept: Some(ContentType::Text),
+            ..Default::default()
+        })?;
 
         let mock = server.mock(|when, then| {
-            when.path("/test")
+            when.path("/admin/test")
+                .header("x-tenant", "child")
+                .header("x-parent", "parent")
+                .header("x-child", "request")
+                .header("authorization", "Bearer child")
                 .header(
                     reqwest::header::ACCEPT.to_string(),
-                    ContentType::Json.to_string(),
+                    ContentType::Text.to_string(),
                 )
-                .header(
-                    reqwest::header::CONTENT_TYPE.to_string(),
-                    ContentType::Json.to_string(),
-                );
+                .header("user-agent", USER_AGENT);
             then.status(200);
         });
 
-        raxios
-            .post::<(), ()>(
+        child
+            .get::<()>(
                 "/test",
-                None,
                 Some(RaxiosOptions {
+                    headers: Some(map_string! {"x-child" => "request"}.into()),
                     deserialize_body: false,
                     ..Default::default()
                 }),
             )
             .await?;
-
         mock.assert_async().await;
+
+        let parent_mock = server.mock(|when, then| {
+            when.path("/test")
+                .header("x-tenant", "acme")
+                .header("authorization", "Bearer parent");
+            then.status(200);
+        });
+        parent
+            .get::<()>(
+                "/test",
+                Some(RaxiosOptions {
+                    deserialize_body: false,
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        parent_mock.assert_async().await;
+
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_raxios_json() -> anyhow::Result<()> {
+    async fn test_clones_share_runtime_defaults() -> anyhow::Result<()> {
         let server = MockServer::start();
-        let raxios = Raxios::new(
-            &server.base_url(),
-            Some(RaxiosConfig {
-                content_type: ContentType::Json,
-                ..Default::default()
-            }),
-        )?;
+        let raxios = Raxios::new("http://localhost:1", None)?;
+        let clone = raxios.clone();
 
-        let mock = server.mock(|when, then| {
-            when.path("/test").method(POST).header(
-                reqwest::header::CONTENT_TYPE.to_string(),
-                ContentType::Json.to_string(),
-            );
+        clone.set_base_url(&server.base_url());
+        clone.set_default_headers(Some(map_string! {"x-tenant" => "acme"}.into()))?;
+        clone.set_auth(Some(crate::RaxiosAuth::Bearer(String::from("refreshed"))))?;
 
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .header("x-tenant", "acme")
+                .header("authorization", "Bearer refreshed")
+                .header("user-agent", USER_AGENT);
             then.status(200);
         });
 
-        raxios
-            .post::<(), HashMap<String, String>>(
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let raxios = raxios.clone();
+                tokio::spawn(async move {
+                    raxios
+                        .get::<()>(
+                            "/test",
+                            Some(RaxiosOptions {
+                                deserialize_body: false,
+                                ..Default::default()
+                            }),
+                        )
+                        .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await??;
+        }
+        mock.assert_hits_async(4).await;
+
+        raxios.set_auth(None)?;
+        assert!(!raxios
+            .defaults()
+            .headers
+            .contains_key(reqwest::header::AUTHORIZATION));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_response_headers_keep_repeated_values() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        server.mock(|when, then| {
+            when.path("/test");
+            then.status(200)
+                .header("Set-Cookie", "a=1")
+                .header("Set-Cookie", "b=2");
+        });
+
+        let response = raxios
+            .get::<()>(
                 "/test",
-                Some(HashMap::new()),
                 Some(RaxiosOptions {
                     deserialize_body: false,
-                    accept: Some(ContentType::Json),
                     ..Default::default()
                 }),
             )
             .await?;
 
-        mock.assert_async().await;
+        assert_eq!(
+            vec!["a=1", "b=2"],
+            response
+                .response_headers
+                .get_all("set-cookie")
+                .collect::<Vec<_>>()
+        );
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_raxios_xml() -> anyhow::Result<()> {
+    async fn test_new_raxios_has_default_headers() -> anyhow::Result<()> {
         let server = MockServer::start();
-        let raxios = Raxios::new(
-            &server.base_url(),
-            Some(RaxiosConfig {
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .header(
+                    reqwest::header::ACCEPT.to_string(),
+                    ContentType::Json.to_string(),
+                )
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Json.to_string(),
+                );
+            then.status(200);
+        });
+
+        raxios
+            .post::<(), ()>(
+                "/test",
+                None,
+                Some(RaxiosOptions {
+                    deserialize_body: false,
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raxios_json() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(
+            &server.base_url(),
+            Some(RaxiosConfig {
+                content_type: ContentType::Json,
+                ..Default::default()
+            }),
+        )?;
+
+        let mock = server.mock(|when, then| {
+            when.path("/test").method(POST).header(
+                reqwest::header::CONTENT_TYPE.to_string(),
+                ContentType::Json.to_string(),
+            );
+
+            then.status(200);
+        });
+
+        raxios
+            .post::<(), HashMap<String, String>>(
+                "/test",
+                Some(HashMap::new()),
+                Some(RaxiosOptions {
+                    deserialize_body: false,
+                    accept: Some(ContentType::Json),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raxios_xml() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(
+            &server.base_url(),
+            Some(RaxiosConfig {
                 accept: ContentType::Json,
                 content_type: ContentType::ApplicationXml,
                 ..Default::default()
@@ -938,4 +1491,420 @@ mod raxios_tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_auto_deserialization_with_params_and_suffix() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let expected_response = NetworkTestResponse {
+            item1: String::from("testing"),
+            item2: String::from("testing2"),
+        };
+
+        let mock = server.mock(|when, then| {
+            when.path("/test").method(GET);
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    "application/vnd.api+json; charset=utf-8",
+                )
+                .json_body_obj(&expected_response);
+        });
+
+        let res = raxios.get::<NetworkTestResponse>("/test", None).await?;
+
+        mock.assert_async().await;
+        assert_eq!(expected_response, res.body.unwrap());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_content_type_is_not_parsed_as_json() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        server.mock(|when, then| {
+            when.path("/test").method(GET);
+            then.status(200)
+                .header(reqwest::header::CONTENT_TYPE.to_string(), "text/html")
+                .body("{}");
+        });
+
+        let res = raxios.get::<HashMap<String, String>>("/test", None).await;
+        assert!(matches!(
+            res,
+            Err(RaxiosError::DeserializationError(
+                DeserializationError::UnsupportedContentType(ref c_type)
+            )) if c_type == "text/html"
+        ));
+
+        let res = raxios
+            .get::<HashMap<String, String>>(
+                "/test",
+                Some(RaxiosOptions {
+                    deserialize_body: false,
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        assert_eq!(b"{}".as_slice(), res.raw_body.unwrap());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raxios_text() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .method(POST)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Text.to_string(),
+                )
+                .body("hello world");
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    "text/plain; charset=utf-8",
+                )
+                .body("hello back");
+        });
+
+        let res = raxios
+            .post::<String, _>(
+                "/test",
+                Some("hello world"),
+                Some(RaxiosOptions {
+                    content_type: Some(ContentType::Text),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!("hello back", res.body.unwrap());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raxios_octet_stream() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+        const PAYLOAD: &[u8] = &[0, 159, 146, 150, 255];
+        let payload = PAYLOAD.to_vec();
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .method(PUT)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::OctetStream.to_string(),
+                )
+                .matches(|req| req.body.as_deref() == Some(PAYLOAD));
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::OctetStream.to_string(),
+                )
+                .body(payload.clone());
+        });
+
+        let res = raxios
+            .put::<_, bytes::Bytes>(
+                "/test",
+                Some(payload.clone()),
+                Some(RaxiosOptions {
+                    content_type: Some(ContentType::OctetStream),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(payload, res.body.unwrap());
+
+        Ok(())
+    }
+
+    /// Reads and writes JSON under a vendor media type
+    struct VendorCodec;
+
+    impl crate::Codec for VendorCodec {
+        fn media_types(&self) -> &[&str] {
+            &["application/vnd.raxios.test"]
+        }
+
+        fn encode(
+            &self,
+            value: &dyn erased_serde::Serialize,
+        ) -> Result<Vec<u8>, crate::SerializationError> {
+            crate::JsonCodec.encode(value)
+        }
+
+        fn decode(
+            &self,
+            body: &[u8],
+            charset: Option<&str>,
+            target: &mut crate::DecodeTarget,
+        ) -> Result<(), crate::DeserializationError> {
+            crate::JsonCodec.decode(body, charset, target)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_raxios_registered_codec() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let mut config = RaxiosConfig::default();
+        config.codecs.register(VendorCodec);
+        let raxios = Raxios::new(&server.base_url(), Some(config))?;
+        let vendor_type = ContentType::Other(String::from("application/vnd.raxios.test"));
+
+        let expected_response = NetworkTestResponse {
+            item1: String::from("testing"),
+            item2: String::from("testing2"),
+        };
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .method(POST)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    vendor_type.to_string(),
+                )
+                .body("{\"item1\":\"testing123\"}");
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    "application/vnd.raxios.test; version=2",
+                )
+                .json_body_obj(&expected_response);
+        });
+
+        let res = raxios
+            .post::<NetworkTestResponse, ToReturn>(
+                "/test",
+                Some(ToReturn {
+                    item1: String::from("testing123"),
+                }),
+                Some(RaxiosOptions {
+                    content_type: Some(vendor_type.clone()),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(expected_response, res.body.unwrap());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn test_raxios_msgpack() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(
+            &server.base_url(),
+            Some(RaxiosConfig {
+                content_type: ContentType::MessagePack,
+                accept: ContentType::MessagePack,
+                ..Default::default()
+            }),
+        )?;
+        let request_body = ToReturn {
+            item1: String::from("testing123"),
+        };
+        let expected_response = NetworkTestResponse {
+            item1: String::from("test"),
+            item2: String::from("test2"),
+        };
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .method(POST)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::MessagePack.to_string(),
+                )
+                .matches(|req| {
+                    req.body
+                        .as_deref()
+                        .map(rmp_serde::from_slice::<ToReturn>)
+                        .and_then(Result::ok)
+                        .map(|body| body.item1 == "testing123")
+                        .unwrap_or(false)
+                });
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    "application/x-msgpack",
+                )
+                .body(rmp_serde::to_vec_named(&expected_response).unwrap());
+        });
+
+        let res = raxios
+            .post::<NetworkTestResponse, ToReturn>("/test", Some(request_body), None)
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(expected_response, res.body.unwrap());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "cbor")]
+    #[tokio::test]
+    async fn test_raxios_cbor() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(
+            &server.base_url(),
+            Some(RaxiosConfig {
+                content_type: ContentType::Cbor,
+                accept: ContentType::Cbor,
+                ..Default::default()
+            }),
+        )?;
+        let expected_response = NetworkTestResponse {
+            item1: String::from("test"),
+            item2: String::from("test2"),
+        };
+        let mut response_body = Vec::new();
+        ciborium::ser::into_writer(&expected_response, &mut response_body)?;
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .method(POST)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Cbor.to_string(),
+                )
+                .matches(|req| {
+                    req.body
+                        .as_deref()
+                        .map(ciborium::de::from_reader::<ToReturn, _>)
+                        .and_then(Result::ok)
+                        .map(|body| body.item1 == "testing123")
+                        .unwrap_or(false)
+                });
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Cbor.to_string(),
+                )
+                .body(response_body);
+        });
+
+        let res = raxios
+            .post::<NetworkTestResponse, ToReturn>(
+                "/test",
+                Some(ToReturn {
+                    item1: String::from("testing123"),
+                }),
+                None,
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(expected_response, res.body.unwrap());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "yaml")]
+    #[tokio::test]
+    async fn test_auto_deserialization_of_yaml() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let expected_response = NetworkTestResponse {
+            item1: String::from("testing"),
+            item2: String::from("testing2"),
+        };
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .method(PUT)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Yaml.to_string(),
+                )
+                .body("item1: testing123\n");
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    "application/x-yaml",
+                )
+                .body("item1: testing\nitem2: testing2\n");
+        });
+
+        let res = raxios
+            .put::<ToReturn, NetworkTestResponse>(
+                "/test",
+                Some(ToReturn {
+                    item1: String::from("testing123"),
+                }),
+                Some(RaxiosOptions {
+                    content_type: Some(ContentType::Yaml),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(expected_response, res.body.unwrap());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[tokio::test]
+    async fn test_auto_deserialization_of_toml() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let expected_response = NetworkTestResponse {
+            item1: String::from("testing"),
+            item2: String::from("testing2"),
+        };
+
+        let mock = server.mock(|when, then| {
+            when.path("/test")
+                .method(PUT)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Toml.to_string(),
+                )
+                .body("item1 = \"testing123\"\n");
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Toml.to_string(),
+                )
+                .body("item1 = \"testing\"\nitem2 = \"testing2\"\n");
+        });
+
+        let res = raxios
+            .put::<ToReturn, NetworkTestResponse>(
+                "/test",
+                Some(ToReturn {
+                    item1: String::from("testing123"),
+                }),
+                Some(RaxiosOptions {
+                    content_type: Some(ContentType::Toml),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(expected_response, res.body.unwrap());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,235 @@
+use std::{fmt::Display, str::FromStr};
+
+/// A parsed media type such as `application/vnd.api+json; charset=utf-8`
+///
+/// The type, subtype and parameter names are lowercased when parsed. Parameter values keep their
+/// original case and have any surrounding quotes removed.
+///
+/// # Example
+/// ```rust
+/// use raxios::MediaType;
+///
+/// let media_type: MediaType = "application/vnd.api+json; charset=UTF-8".parse().unwrap();
+/// assert_eq!("application/vnd.api+json", media_type.essence());
+/// assert_eq!(Some("json"), media_type.suffix());
+/// assert_eq!(Some("UTF-8"), media_type.charset());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaType {
+    type_: String,
+    subtype: String,
+    params: Vec<(String, String)>,
+}
+
+impl MediaType {
+    /// The top level type, e.g. `application` for `application/json`
+    pub fn type_(&self) -> &str {
+        &self.type_
+    }
+
+    /// The subtype including any suffix, e.g. `vnd.api+json` for `application/vnd.api+json`
+    pub fn subtype(&self) -> &str {
+        &self.subtype
+    }
+
+    /// The structured syntax suffix, e.g. `json` for `application/hal+json`
+    pub fn suffix(&self) -> Option<&str> {
+        self.subtype
+            .rsplit_once('+')
+            .map(|(_, suffix)| suffix)
+            .filter(|suffix| !suffix.is_empty())
+    }
+
+    /// The media type without any parameters, e.g. `application/json`
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.type_, self.subtype)
+    }
+
+    /// Looks up a parameter by its (case-insensitive) name
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All parameters in the order they were supplied
+    pub fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+
+    /// The `charset` parameter, if present
+    pub fn charset(&self) -> Option<&str> {
+        self.param("charset")
+    }
+
+    /// The `boundary` parameter, if present
+    pub fn boundary(&self) -> Option<&str> {
+        self.param("boundary")
+    }
+}
+
+pub(crate) fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+/// Splits on `;` while ignoring any `;` that appears inside of a quoted string
+pub(crate) fn split_params(s: &str) -> Vec<&str> {
+    split_unquoted(s, ';')
+}
+
+/// Splits on `delimiter` while ignoring any `delimiter` that appears inside of a quoted string
+pub(crate) fn split_unquoted(s: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => {
+                parts.push(&s[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Wraps `value` in a quoted string, escaping any `\\` and `"`
+pub(crate) fn quote(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+pub(crate) fn unquote(value: &str) -> Option<String> {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let mut unquoted = String::new();
+        let mut chars = value[1..value.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                unquoted.push(chars.next()?);
+            } else {
+                unquoted.push(c);
+            }
+        }
+        Some(unquoted)
+    } else if is_token(value) {
+        Some(value.to_owned())
+    } else {
+        None
+    }
+}
+
+impl FromStr for MediaType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = split_params(s).into_iter();
+        let (type_, subtype) = parts
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .split_once('/')
+            .ok_or(())?;
+        if !is_token(type_) || !is_token(subtype) {
+            return Err(());
+        }
+
+        let mut params = Vec::new();
+        for param in parts {
+            let param = param.trim();
+            if param.is_empty() {
+                continue;
+            }
+            let (key, value) = param.split_once('=').ok_or(())?;
+            let key = key.trim();
+            if !is_token(key) {
+                return Err(());
+            }
+            params.push((key.to_ascii_lowercase(), unquote(value.trim()).ok_or(())?));
+        }
+
+        Ok(Self {
+            type_: type_.to_ascii_lowercase(),
+            subtype: subtype.to_ascii_lowercase(),
+            params,
+        })
+    }
+}
+
+impl Display for MediaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.type_, self.subtype)?;
+        for (key, value) in &self.params {
+            if is_token(value) {
+                write!(f, "; {key}={value}")?;
+            } else {
+                write!(f, "; {key}={}", quote(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MediaType;
+
+    #[test]
+    fn test_parse_simple_media_type() {
+        let media_type: MediaType = "Application/JSON".parse().unwrap();
+        assert_eq!("application", media_type.type_());
+        assert_eq!("json", media_type.subtype());
+        assert_eq!(None, media_type.suffix());
+        assert!(media_type.params().is_empty());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let media_type: MediaType = "multipart/form-data; charset=utf-8; boundary=\"a;b\\\"c\""
+            .parse()
+            .unwrap();
+        assert_eq!(Some("utf-8"), media_type.charset());
+        assert_eq!(Some("a;b\"c"), media_type.boundary());
+        assert_eq!(Some("utf-8"), media_type.param("CHARSET"));
+    }
+
+    #[test]
+    fn test_parse_suffix() {
+        let media_type: MediaType = "application/hal+json".parse().unwrap();
+        assert_eq!(Some("json"), media_type.suffix());
+        assert_eq!("application/hal+json", media_type.essence());
+    }
+
+    #[test]
+    fn test_parse_invalid_media_types() {
+        assert!("".parse::<MediaType>().is_err());
+        assert!("application".parse::<MediaType>().is_err());
+        assert!("application/".parse::<MediaType>().is_err());
+        assert!("application/json; charset".parse::<MediaType>().is_err());
+        assert!("application/json; charset=\"utf-8"
+            .parse::<MediaType>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let media_type: MediaType = "text/plain;charset=utf-8; name=\"a b\"".parse().unwrap();
+        assert_eq!(
+            "text/plain; charset=utf-8; name=\"a b\"",
+            media_type.to_string()
+        );
+        assert_eq!(media_type, media_type.to_string().parse().unwrap());
+    }
+}
@@ -0,0 +1,355 @@
+use bytes::{Bytes, BytesMut};
+use futures_util::{stream, Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    ContentType, DeserializationError, Raxios, RaxiosError, RaxiosOptions, RaxiosResponse,
+    RaxiosResult, RaxiosStream, SerializationError,
+};
+
+/// ASCII record separator, used to delimit JSON text sequences (`application/json-seq`)
+const RECORD_SEPARATOR: u8 = 0x1E;
+
+fn is_blank(record: &[u8]) -> bool {
+    record.iter().all(u8::is_ascii_whitespace)
+}
+
+/// Splits a streamed body into newline (or record separator) delimited records as bytes arrive.
+/// Blank records are skipped, and a trailing record without a delimiter is still yielded.
+pub(crate) fn split_records<S>(body: S) -> impl Stream<Item = RaxiosResult<Bytes>> + Send
+where
+    S: Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+{
+    let state = (Box::pin(body), BytesMut::new(), false);
+
+    stream::unfold(state, |(mut body, mut buffer, mut done)| async move {
+        loop {
+            if let Some(index) = buffer
+                .iter()
+                .position(|byte| *byte == b'\n' || *byte == RECORD_SEPARATOR)
+            {
+                let mut record = buffer.split_to(index + 1);
+                record.truncate(index);
+                if is_blank(&record) {
+                    continue;
+                }
+                return Some((Ok(record.freeze()), (body, buffer, done)));
+            }
+
+            if done {
+                let record = buffer.split();
+                if is_blank(&record) {
+                    return None;
+                }
+                return Some((Ok(record.freeze()), (body, buffer, done)));
+            }
+
+            match body.next().await {
+                Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                Some(Err(err)) => {
+                    buffer.clear();
+                    return Some((
+                        Err(RaxiosError::UnableToSendRequest { err }),
+                        (body, buffer, true),
+                    ));
+                }
+                None => done = true,
+            }
+        }
+    })
+}
+
+/// Serializes every item of `data` as a single line of JSON
+pub(crate) fn to_ndjson<U, I>(data: I) -> RaxiosResult<Vec<u8>>
+where
+    U: Serialize,
+    I: IntoIterator<Item = U>,
+{
+    let mut body = Vec::new();
+    for item in data {
+        serde_json::to_writer(&mut body, &item)
+            .map_err(|e| RaxiosError::SerializationError(SerializationError::Json(e)))?;
+        body.push(b'\n');
+    }
+    Ok(body)
+}
+
+/// Deserializes a fully buffered NDJSON body as if it were a JSON array of its records
+pub(crate) fn from_ndjson<T>(raw_body: &[u8]) -> RaxiosResult<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let records = raw_body
+        .split(|byte| *byte == b'\n' || *byte == RECORD_SEPARATOR)
+        .filter(|record| !is_blank(record))
+        .map(serde_json::from_slice::<serde_json::Value>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))?;
+
+    T::deserialize(serde_json::Value::Array(records))
+        .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))
+}
+
+impl Raxios {
+    /// Sends an HTTP GET request and streams the `application/x-ndjson` response, deserializing one
+    /// record per line as bytes arrive. JSON text sequences (`application/json-seq`) are also supported.
+    ///
+    /// A record which fails to deserialize yields an `Err` without ending the stream.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures_util::StreamExt;
+    /// use httpmock::prelude::*;
+    /// use raxios::Raxios;
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct LogLine {
+    ///     message: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/logs").method(GET);
+    ///         then.status(200)
+    ///             .header("content-type", "application/x-ndjson")
+    ///             .body("{\"message\":\"one\"}\n{\"message\":\"two\"}\n");
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let mut lines = raxios.get_ndjson::<LogLine>("/logs", None).await.unwrap();
+    ///
+    ///     assert_eq!("one", lines.next().await.unwrap().unwrap().message);
+    ///     assert_eq!("two", lines.next().await.unwrap().unwrap().message);
+    ///     assert!(lines.next().await.is_none());
+    /// }
+    /// ```
+    pub async fn get_ndjson<T>(
+        &self,
+        endpoint: &str,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosStream<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut options = options.unwrap_or_default();
+        options.accept.get_or_insert(ContentType::NdJson);
+
+        let response = self
+            .build_raw_request::<Vec<u8>>(
+                None,
+                Some(&options),
+                self.client.get(self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+        let response = self.check_response_and_return_err(response).await?;
+
+        Ok(Box::pin(split_records(response.bytes_stream()).map(
+            |record| {
+                serde_json::from_slice::<T>(&record?)
+                    .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))
+            },
+        )))
+    }
+
+    /// Sends an HTTP POST request with an `application/x-ndjson` body, one line per item of `data`
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `data` - The items to send to the remote endpoint
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    pub async fn post_ndjson<T, U, I>(
+        &self,
+        endpoint: &str,
+        data: I,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+        U: Serialize,
+        I: IntoIterator<Item = U>,
+    {
+        let options = options.unwrap_or_default();
+        let response = self
+            .build_raw_request(
+                Some((to_ndjson(data)?, ContentType::NdJson)),
+                Some(&options),
+                self.client.post(self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+
+        self.response_to_raxios_response(response, &options).await
+    }
+
+    /// Sends an HTTP POST request with an `application/x-ndjson` body which is streamed to the
+    /// remote server as items of `data` become available.
+    ///
+    /// Since the body is serialized while it is being sent, an item which fails to serialize aborts
+    /// the request with `RaxiosError::UnableToSendRequest`.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `data` - The stream of items to send to the remote endpoint
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    pub async fn post_ndjson_stream<T, U, S>(
+        &self,
+        endpoint: &str,
+        data: S,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+        U: Serialize,
+        S: Stream<Item = U> + Send + Sync + 'static,
+    {
+        let options = options.unwrap_or_default();
+        let body = reqwest::Body::wrap_stream(data.map(|item| {
+            let mut line = serde_json::to_vec(&item)?;
+            line.push(b'\n');
+            Ok::<_, serde_json::Error>(Bytes::from(line))
+        }));
+
+        let response = self
+            .build_raw_request(
+                Some((body, ContentType::NdJson)),
+                Some(&options),
+                self.client.post(self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+
+        self.response_to_raxios_response(response, &options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::{stream, StreamExt};
+    use httpmock::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use super::{from_ndjson, split_records};
+    use crate::{ContentType, Raxios, RaxiosError};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record {
+        id: u32,
+    }
+
+    #[tokio::test]
+    async fn test_split_records_across_chunks() {
+        let chunks = vec![
+            Ok(Bytes::from_static(b"{\"id\":1}\n{\"i")),
+            Ok(Bytes::from_static(b"d\":2}\n\n\x1e{\"id\":3}\r\n")),
+            Ok(Bytes::from_static(b"{\"id\":4}")),
+        ];
+        let records: Vec<Bytes> = split_records(stream::iter(chunks))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(
+            vec![
+                Bytes::from_static(b"{\"id\":1}"),
+                Bytes::from_static(b"{\"id\":2}"),
+                Bytes::from_static(b"{\"id\":3}\r"),
+                Bytes::from_static(b"{\"id\":4}"),
+            ],
+            records
+        );
+    }
+
+    #[test]
+    fn test_from_ndjson() {
+        let records: Vec<Record> = from_ndjson(b"{\"id\":1}\n\n{\"id\":2}\n").unwrap();
+        assert_eq!(vec![Record { id: 1 }, Record { id: 2 }], records);
+    }
+
+    #[tokio::test]
+    async fn test_get_ndjson_yields_errors_without_ending() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let mock = server.mock(|when, then| {
+            when.path("/export").method(GET).header(
+                reqwest::header::ACCEPT.to_string(),
+                ContentType::NdJson.to_string(),
+            );
+            then.status(200)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::NdJson.to_string(),
+                )
+                .body("{\"id\":1}\nnot json\n{\"id\":3}\n");
+        });
+
+        let records: Vec<_> = raxios
+            .get_ndjson::<Record>("/export", None)
+            .await?
+            .collect()
+            .await;
+
+        mock.assert_async().await;
+        assert_eq!(3, records.len());
+        assert_eq!(&Record { id: 1 }, records[0].as_ref().unwrap());
+        assert!(matches!(
+            records[1],
+            Err(RaxiosError::DeserializationError(_))
+        ));
+        assert_eq!(&Record { id: 3 }, records[2].as_ref().unwrap());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_ndjson() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        let mock = server.mock(|when, then| {
+            when.path("/import")
+                .method(POST)
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::NdJson.to_string(),
+                )
+                .body("{\"id\":1}\n{\"id\":2}\n");
+            then.status(200);
+        });
+
+        raxios
+            .post_ndjson::<(), _, _>(
+                "/import",
+                vec![Record { id: 1 }, Record { id: 2 }],
+                Some(crate::RaxiosOptions {
+                    deserialize_body: false,
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        mock.assert_async().await;
+
+        raxios
+            .post_ndjson_stream::<(), _, _>(
+                "/import",
+                stream::iter(vec![Record { id: 1 }, Record { id: 2 }]),
+                Some(crate::RaxiosOptions {
+                    deserialize_body: false,
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        mock.assert_hits_async(2).await;
+
+        Ok(())
+    }
+}
@@ -4,6 +4,8 @@ use bytes::Bytes;
 use reqwest::{Response, StatusCode};
 use thiserror::Error;
 
+use crate::{utils::reqwest_headers_to_map, RaxiosHeaders};
+
 /// Represents any non-200 HTTP status code
 ///
 /// # Example
@@ -27,6 +29,7 @@ use thiserror::Error;
 ///         assert_eq!(400, err.status_code);
 ///         assert_eq!(Some(*server.address()), err.origin_address);
 ///         assert_eq!(false, err.raw_body.is_none());
+///         assert_eq!(Some(0), err.content_length().unwrap());
 ///     } else {
 ///         panic!("Result was not an instance of NetworkError");
 ///     }
@@ -34,11 +37,12 @@ use thiserror::Error;
 ///     assert!(response.is_err());
 /// }
 /// ```
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub struct NetworkError {
     pub status_code: StatusCode,
     pub origin_address: Option<SocketAddr>,
     pub raw_body: Option<Bytes>,
+    pub response_headers: RaxiosHeaders,
 }
 
 impl Display for NetworkError {
@@ -67,7 +71,16 @@ impl NetworkError {
         Self {
             status_code: response.status(),
             origin_address: response.remote_addr(),
+            response_headers: reqwest_headers_to_map(response.headers()),
             raw_body: response.bytes().await.ok(),
         }
     }
+
+    /// Returns `response`, or a `NetworkError` read from it when its status is 4xx or 5xx
+    pub(crate) async fn check(response: Response) -> Result<Response, Self> {
+        if response.status().is_client_error() || response.status().is_server_error() {
+            return Err(Self::new(response).await);
+        }
+        Ok(response)
+    }
 }
@@ -0,0 +1,513 @@
+use std::collections::{HashSet, VecDeque};
+
+use futures_util::stream;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    DeserializationError, QueryParams, Raxios, RaxiosError, RaxiosOptions, RaxiosResult,
+    RaxiosStream,
+};
+
+/// How the next page of a paginated endpoint is requested
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageStrategy {
+    /// Follow the `rel="next"` link of the RFC 8288 `link` header, as GitHub does, until there is
+    /// none. A next link to another origin is refused with an error, so that the default headers
+    /// (and any credentials among them) are never sent to a server other than the first one.
+    Link,
+    /// Send the cursor found in the body of each page as the query parameter `param`, until the
+    /// cursor is missing, `null` or an empty string. A page with no items does not end the stream
+    /// while it still has a cursor.
+    Cursor {
+        /// The query parameter the cursor is sent as, such as `cursor` or `after`
+        param: String,
+        /// A JSON pointer to the cursor of the next page in the body, such as `/meta/next_cursor`
+        pointer: String,
+    },
+    /// Send the number of items already received as the query parameter `param`, starting at 0
+    Offset { param: String },
+    /// Send the page number as the query parameter `param`, starting at `first_page`
+    PageNumber { param: String, first_page: u64 },
+}
+
+/// Describes how to walk a paginated endpoint with `Raxios::paginate`
+///
+/// The `Offset` and `PageNumber` strategies stop at the first page with no items, or with fewer
+/// items than `page_size` when it is set. A next link or cursor which leads back to a page that was
+/// already requested yields an `Err` and ends the stream, rather than looping forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pagination {
+    pub strategy: PageStrategy,
+    /// A JSON pointer to the array of items in the body of each page, such as `/data`. The
+    /// default, an empty pointer, means the body is the array.
+    pub items_pointer: String,
+    /// A query parameter and value to request pages of a given size with, such as `("per_page", 100)`
+    pub page_size: Option<(String, u64)>,
+    /// Stop after this many pages have been requested
+    pub max_pages: Option<usize>,
+}
+
+impl Pagination {
+    pub fn new(strategy: PageStrategy) -> Self {
+        Self {
+            strategy,
+            items_pointer: String::new(),
+            page_size: None,
+            max_pages: None,
+        }
+    }
+}
+
+struct PageState<T> {
+    raxios: Raxios,
+    pagination: Pagination,
+    /// The endpoint and options of the next page, or `None` once the last page has been requested
+    next: Option<(String, RaxiosOptions)>,
+    items: VecDeque<RaxiosResult<T>>,
+    pages: usize,
+    /// The url of every page requested so far
+    requested: HashSet<String>,
+    /// The offset or page number of the next page
+    position: u64,
+}
+
+impl<T> PageState<T>
+where
+    T: DeserializeOwned,
+{
+    async fn fetch_page(&mut self, endpoint: String, options: RaxiosOptions) -> RaxiosResult<()> {
+        let url = self.raxios.build_url(&endpoint, Some(&options))?;
+        if !self.requested.insert(url.to_string()) {
+            return Err(RaxiosError::InvalidUrl(format!(
+                "{url} (page was already requested)"
+            )));
+        }
+        self.pages += 1;
+        let response = self
+            .raxios
+            .get::<Value>(url.as_str(), Some(without_url_parts(options.clone())))
+            .await?;
+        let body = response.body.clone().unwrap_or_default();
+
+        let items = match body.pointer(&self.pagination.items_pointer) {
+            Some(Value::Array(items)) => items.clone(),
+            _ => {
+                return Err(RaxiosError::DeserializationError(
+                    DeserializationError::Unknown(format!(
+                        "Expected an array of items at `{}`",
+                        self.pagination.items_pointer
+                    )),
+                ))
+            }
+        };
+        let count = items.len() as u64;
+        self.items.extend(items.into_iter().map(|item| {
+            serde_json::from_value(item)
+                .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))
+        }));
+
+        let is_last_page =
+            count == 0 || matches!(self.pagination.page_size, Some((_, size)) if count < size);
+        self.next = match &self.pagination.strategy {
+            PageStrategy::Link => match response.link("next")? {
+                Some(link) => {
+                    let next_url = url.join(&link.target).map_err(|_| {
+                        RaxiosError::InvalidUrl(format!("{} (invalid next link)", link.target))
+                    })?;
+                    if next_url.origin() != url.origin() {
+                        return Err(RaxiosError::InvalidUrl(format!(
+                            "{next_url} (next link leaves the origin of {url})"
+                        )));
+                    }
+                    // The next link already carries every query parameter of the page
+                    Some((next_url.to_string(), without_url_parts(options)))
+                }
+                None => None,
+            },
+            PageStrategy::Cursor { param, pointer } => {
+                let cursor = match body.pointer(pointer) {
+                    Some(Value::String(cursor)) if !cursor.is_empty() => Some(cursor.clone()),
+                    Some(Value::Number(cursor)) => Some(cursor.to_string()),
+                    _ => None,
+                };
+                cursor.map(|cursor| (endpoint, with_query_param(options, param, cursor)))
+            }
+            PageStrategy::Offset { param } if !is_last_page => {
+                self.position += self
+                    .pagination
+                    .page_size
+                    .as_ref()
+                    .map(|(_, size)| *size)
+                    .unwrap_or(count);
+                Some((endpoint, with_query_param(options, param, self.position)))
+            }
+            PageStrategy::PageNumber { param, .. } if !is_last_page => {
+                self.position += 1;
+                Some((endpoint, with_query_param(options, param, self.position)))
+            }
+            PageStrategy::Offset { .. } | PageStrategy::PageNumber { .. } => None,
+        };
+
+        Ok(())
+    }
+}
+
+/// `options` without what `Raxios::build_url` has already applied to a url
+fn without_url_parts(options: RaxiosOptions) -> RaxiosOptions {
+    RaxiosOptions {
+        params: None,
+        query: None,
+        path_params: None,
+        uri_variables: None,
+        ..options
+    }
+}
+
+fn with_query_param(
+    mut options: RaxiosOptions,
+    param: &str,
+    value: impl ToString,
+) -> RaxiosOptions {
+    options
+        .query
+        .get_or_insert_with(QueryParams::new)
+        .insert(param, value);
+    options
+}
+
+impl Raxios {
+    /// Sends HTTP GET requests for every page of a paginated endpoint, and streams the items of
+    /// each page in order. No request is sent until the stream is polled, and each page is only
+    /// requested once the items of the previous page have been consumed.
+    ///
+    /// Every page is deserialized into a `serde_json::Value`, so any content type which can be
+    /// deserialized (including those of registered codecs) can be paginated. An item which fails to
+    /// deserialize into `T` yields an `Err` without ending the stream, while a page which fails to
+    /// load yields an `Err` and ends it.
+    ///
+    /// The query parameters of the `Cursor`, `Offset` and `PageNumber` strategies are set on
+    /// `options.query`, replacing any parameter of the same name.
+    ///
+    /// * `endpoint` - The remote endpoint of the first page. This gets joined with the base_url configured in the ::new() method
+    /// * `pagination` - How to find the items of a page and request the next one
+    /// * `options` - The `RaxiosOptions` for every page. Allows setting of headers and/or query params
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures_util::StreamExt;
+    /// use httpmock::prelude::*;
+    /// use raxios::{PageStrategy, Pagination, Raxios};
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct User {
+    ///     id: u32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/users").method(GET).query_param("page", "1");
+    ///         then.status(200)
+    ///             .header("link", "</users?page=2>; rel=\"next\"")
+    ///             .json_body(serde_json::json!([{"id": 1}, {"id": 2}]));
+    ///     });
+    ///     server.mock(|when, then| {
+    ///         when.path("/users").method(GET).query_param("page", "2");
+    ///         then.status(200).json_body(serde_json::json!([{"id": 3}]));
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let users = raxios
+    ///         .paginate::<User>("/users?page=1", Pagination::new(PageStrategy::Link), None)
+    ///         .map(Result::unwrap)
+    ///         .collect::<Vec<_>>()
+    ///         .await;
+    ///
+    ///     assert_eq!(vec![User { id: 1 }, User { id: 2 }, User { id: 3 }], users);
+    /// }
+    /// ```
+    pub fn paginate<T>(
+        &self,
+        endpoint: &str,
+        pagination: Pagination,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosStream<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut options = options.unwrap_or_default();
+        if let Some((param, size)) = &pagination.page_size {
+            options = with_query_param(options, param, size);
+        }
+        let position = match &pagination.strategy {
+            PageStrategy::Offset { param } => {
+                options = with_query_param(options, param, 0);
+                0
+            }
+            PageStrategy::PageNumber { param, first_page } => {
+                options = with_query_param(options, param, first_page);
+                *first_page
+            }
+            PageStrategy::Link | PageStrategy::Cursor { .. } => 0,
+        };
+
+        let state = PageState {
+            raxios: self.clone(),
+            pagination,
+            next: Some((endpoint.to_owned(), options)),
+            items: VecDeque::new(),
+            pages: 0,
+            requested: HashSet::new(),
+            position,
+        };
+
+        Box::pin(stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.items.pop_front() {
+                    return Some((item, state));
+                }
+                if matches!(state.pagination.max_pages, Some(max_pages) if state.pages >= max_pages)
+                {
+                    return None;
+                }
+
+                let (endpoint, options) = state.next.take()?;
+                if let Err(err) = state.fetch_page(endpoint, options).await {
+                    state.next = None;
+                    state.items.push_back(Err(err));
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use httpmock::prelude::*;
+    use serde::Deserialize;
+
+    use super::{PageStrategy, Pagination};
+    use crate::{Raxios, RaxiosError};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+
+    async fn ids(raxios: &Raxios, endpoint: &str, pagination: Pagination) -> Vec<u32> {
+        raxios
+            .paginate::<Item>(endpoint, pagination, None)
+            .map(|item| item.unwrap().id)
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_paginate_cursor_in_body() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.path("/events").matches(|req| {
+                !req.query_params
+                    .iter()
+                    .flatten()
+                    .any(|(key, _)| key == "after")
+            });
+            then.status(200)
+                .json_body(serde_json::json!({"data": [{"id": 1}], "meta": {"next": "c2"}}));
+        });
+        let last = server.mock(|when, then| {
+            when.path("/events").query_param("after", "c2");
+            then.status(200)
+                .json_body(serde_json::json!({"data": [{"id": 2}], "meta": {"next": null}}));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let pagination = Pagination {
+            items_pointer: String::from("/data"),
+            ..Pagination::new(PageStrategy::Cursor {
+                param: String::from("after"),
+                pointer: String::from("/meta/next"),
+            })
+        };
+
+        assert_eq!(vec![1, 2], ids(&raxios, "/events", pagination).await);
+        first.assert();
+        last.assert();
+    }
+
+    #[tokio::test]
+    async fn test_paginate_cursor_continues_past_empty_page() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/events").matches(|req| {
+                !req.query_params
+                    .iter()
+                    .flatten()
+                    .any(|(key, _)| key == "after")
+            });
+            then.status(200)
+                .json_body(serde_json::json!({"data": [], "meta": {"next": "c2"}}));
+        });
+        let last = server.mock(|when, then| {
+            when.path("/events").query_param("after", "c2");
+            then.status(200)
+                .json_body(serde_json::json!({"data": [{"id": 2}], "meta": {}}));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let pagination = Pagination {
+            items_pointer: String::from("/data"),
+            ..Pagination::new(PageStrategy::Cursor {
+                param: String::from("after"),
+                pointer: String::from("/meta/next"),
+            })
+        };
+
+        assert_eq!(vec![2], ids(&raxios, "/events", pagination).await);
+        last.assert();
+    }
+
+    #[tokio::test]
+    async fn test_paginate_offset_stops_at_short_page() {
+        let server = MockServer::start();
+        for (offset, body) in [
+            ("0", serde_json::json!([{"id": 1}, {"id": 2}])),
+            ("2", serde_json::json!([{"id": 3}])),
+        ] {
+            server.mock(|when, then| {
+                when.path("/items")
+                    .query_param("offset", offset)
+                    .query_param("limit", "2");
+                then.status(200).json_body(body);
+            });
+        }
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let pagination = Pagination {
+            page_size: Some((String::from("limit"), 2)),
+            ..Pagination::new(PageStrategy::Offset {
+                param: String::from("offset"),
+            })
+        };
+
+        assert_eq!(vec![1, 2, 3], ids(&raxios, "/items", pagination).await);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_page_number_respects_max_pages() {
+        let server = MockServer::start();
+        let pages = server.mock(|when, then| {
+            when.path("/items");
+            then.status(200).json_body(serde_json::json!([{"id": 1}]));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let pagination = Pagination {
+            max_pages: Some(3),
+            ..Pagination::new(PageStrategy::PageNumber {
+                param: String::from("page"),
+                first_page: 1,
+            })
+        };
+
+        assert_eq!(vec![1, 1, 1], ids(&raxios, "/items", pagination).await);
+        pages.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_refuses_cross_origin_link() {
+        let other = MockServer::start();
+        let leaked = other.mock(|when, then| {
+            when.path("/items");
+            then.status(200).json_body(serde_json::json!([{"id": 2}]));
+        });
+        let server = MockServer::start();
+        let link = format!("<{}>; rel=next", other.url("/items"));
+        server.mock(|when, then| {
+            when.path("/items");
+            then.status(200)
+                .header("link", link.as_str())
+                .json_body(serde_json::json!([{"id": 1}]));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let mut items =
+            raxios.paginate::<Item>("/items", Pagination::new(PageStrategy::Link), None);
+
+        assert_eq!(Item { id: 1 }, items.next().await.unwrap().unwrap());
+        assert!(matches!(
+            items.next().await,
+            Some(Err(RaxiosError::InvalidUrl(_)))
+        ));
+        assert!(items.next().await.is_none());
+        leaked.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_detects_repeated_pages() {
+        let server = MockServer::start();
+        let pages = server.mock(|when, then| {
+            when.path("/items");
+            then.status(200)
+                .header("link", "</items?page=1>; rel=next")
+                .json_body(serde_json::json!({"data": [{"id": 1}], "next": "c1"}));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let cursor = Pagination {
+            items_pointer: String::from("/data"),
+            ..Pagination::new(PageStrategy::Cursor {
+                param: String::from("after"),
+                pointer: String::from("/next"),
+            })
+        };
+        let link = Pagination {
+            items_pointer: String::from("/data"),
+            ..Pagination::new(PageStrategy::Link)
+        };
+
+        // The cursor repeats on the second page, while the link points back to the first page
+        for (endpoint, pagination, ok_items) in [("/items", cursor, 2), ("/items?page=1", link, 1)]
+        {
+            let items = raxios
+                .paginate::<Item>(endpoint, pagination, None)
+                .collect::<Vec<_>>()
+                .await;
+
+            assert_eq!(ok_items + 1, items.len());
+            assert!(items[..ok_items].iter().all(|item| item.is_ok()));
+            assert!(matches!(items[ok_items], Err(RaxiosError::InvalidUrl(_))));
+        }
+        pages.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_ends_on_failed_page() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/items").query_param("page", "1");
+            then.status(200)
+                .header("link", "<?page=2>; rel=next")
+                .json_body(serde_json::json!([{"id": 1}]));
+        });
+        server.mock(|when, then| {
+            when.path("/items").query_param("page", "2");
+            then.status(500);
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let mut items =
+            raxios.paginate::<Item>("/items?page=1", Pagination::new(PageStrategy::Link), None);
+
+        assert_eq!(Item { id: 1 }, items.next().await.unwrap().unwrap());
+        assert!(matches!(
+            items.next().await,
+            Some(Err(RaxiosError::NetworkError(_)))
+        ));
+        assert!(items.next().await.is_none());
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{RaxiosError, RaxiosResult, SerializationError};
+
+/// Everything except the unreserved characters of RFC 3986 is percent-encoded, so a value can never
+/// add a `/`, `?` or `#` to the path
+const PATH_SEGMENT_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Values for the `{name}` placeholders of a templated endpoint such as `/users/{id}`
+///
+/// # Example
+/// ```rust
+/// use raxios::PathParams;
+///
+/// #[derive(serde::Serialize)]
+/// struct OrderPath {
+///     id: u32,
+///     order_id: &'static str,
+/// }
+///
+/// let params = PathParams::from_serialize(&OrderPath { id: 7, order_id: "a/b" }).unwrap();
+/// assert_eq!(Some("7"), params.get("id"));
+/// assert_eq!(Some("a/b"), params.get("order_id"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathParams {
+    params: HashMap<String, String>,
+}
+
+impl PathParams {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the value of the `{name}` placeholder, replacing any previous value
+    pub fn insert(&mut self, name: impl Into<String>, value: impl ToString) -> &mut Self {
+        self.params.insert(name.into(), value.to_string());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// Reads the placeholder values from the fields of a struct or map. Fields must be strings,
+    /// numbers or booleans; `None` fields are skipped.
+    pub fn from_serialize<T>(value: &T) -> Result<Self, SerializationError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let fields = match serde_json::to_value(value).map_err(SerializationError::Json)? {
+            Value::Object(fields) => fields,
+            _ => {
+                return Err(SerializationError::Unknown(String::from(
+                    "Path parameters must serialize to a struct or map",
+                )))
+            }
+        };
+
+        let mut params = Self::new();
+        for (name, value) in fields {
+            match value {
+                Value::Null => {}
+                Value::String(value) => {
+                    params.insert(name, value);
+                }
+                Value::Number(_) | Value::Bool(_) => {
+                    params.insert(name, value);
+                }
+                Value::Array(_) | Value::Object(_) => {
+                    return Err(SerializationError::Unknown(format!(
+                        "Path parameter `{name}` must be a string, number or boolean"
+                    )))
+                }
+            }
+        }
+        Ok(params)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for PathParams
+where
+    K: Into<String>,
+    V: ToString,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut params = Self::new();
+        for (name, value) in iter {
+            params.insert(name, value);
+        }
+        params
+    }
+}
+
+impl From<HashMap<String, String>> for PathParams {
+    fn from(params: HashMap<String, String>) -> Self {
+        Self { params }
+    }
+}
+
+/// Replaces every `{name}` placeholder in the path of `endpoint` with its percent-encoded value.
+/// Anything after the path (the query and fragment) is left untouched. Without `params`, the
+/// endpoint is not treated as a template, so literal braces pass through.
+pub(crate) fn expand_path(endpoint: &str, params: Option<&PathParams>) -> RaxiosResult<String> {
+    let Some(params) = params else {
+        return Ok(endpoint.to_string());
+    };
+    let path_end = endpoint.find(['?', '#']).unwrap_or(endpoint.len());
+    let (path, rest) = endpoint.split_at(path_end);
+
+    let segments = path
+        .split('/')
+        .map(|segment| expand_segment(endpoint, segment, params))
+        .collect::<RaxiosResult<Vec<_>>>()?;
+
+    Ok(segments.join("/") + rest)
+}
+
+/// Expands the placeholders of a single path segment. A segment which expands to `.` or `..` is
+/// rejected, because url parsing would resolve it and let a value climb out of its segment, even
+/// when the dots are percent-encoded.
+fn expand_segment(endpoint: &str, segment: &str, params: &PathParams) -> RaxiosResult<String> {
+    let mut expanded = String::with_capacity(segment.len());
+    let mut remaining = segment;
+    while let Some(start) = remaining.find('{') {
+        expanded += &remaining[..start];
+        let end = remaining[start..].find('}').ok_or_else(|| {
+            RaxiosError::InvalidUrl(format!("{endpoint} (unclosed path parameter)"))
+        })? + start;
+
+        let name = &remaining[start + 1..end];
+        let value = params.get(name).ok_or_else(|| {
+            RaxiosError::InvalidUrl(format!("{endpoint} (missing path parameter `{name}`)"))
+        })?;
+        expanded.extend(utf8_percent_encode(value, PATH_SEGMENT_ENCODE_SET));
+        remaining = &remaining[end + 1..];
+    }
+    if remaining.len() == segment.len() {
+        return Ok(segment.to_string());
+    }
+    expanded += remaining;
+
+    if matches!(
+        &*percent_decode_str(&expanded).decode_utf8_lossy(),
+        "." | ".."
+    ) {
+        return Err(RaxiosError::InvalidUrl(format!(
+            "{endpoint} (path parameters expand to the dot segment `{expanded}`)"
+        )));
+    }
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{expand_path, PathParams};
+    use crate::RaxiosError;
+
+    #[test]
+    fn test_expand_path_encodes_segments() {
+        let params: PathParams = [("id", "a/b c"), ("order_id", "1?x#y")]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            "/users/a%2Fb%20c/orders/1%3Fx%23y?expand={id}",
+            expand_path("/users/{id}/orders/{order_id}?expand={id}", Some(&params)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_expand_path_without_placeholders() {
+        assert_eq!("/users", expand_path("/users", None).unwrap());
+        assert_eq!(
+            "/files/{literal}",
+            expand_path("/files/{literal}", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_expand_path_missing_placeholder() {
+        let params: PathParams = [("id", 1)].into_iter().collect();
+        assert!(matches!(
+            expand_path("/users/{id}/orders/{order_id}", Some(&params)),
+            Err(RaxiosError::InvalidUrl(ref message)) if message.contains("`order_id`")
+        ));
+        assert!(matches!(
+            expand_path("/users/{id", Some(&params)),
+            Err(RaxiosError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn test_expand_path_rejects_dot_segments() {
+        for value in [".", ".."] {
+            let params: PathParams = [("id", value)].into_iter().collect();
+            assert!(
+                matches!(
+                    expand_path("/users/{id}/orders", Some(&params)),
+                    Err(RaxiosError::InvalidUrl(_))
+                ),
+                "{value}"
+            );
+        }
+
+        let params: PathParams = [("a", "."), ("b", ".")].into_iter().collect();
+        assert!(expand_path("/files/{a}{b}", Some(&params)).is_err());
+        assert_eq!(
+            "/files/...",
+            expand_path("/files/{a}{b}.", Some(&params)).unwrap()
+        );
+        assert_eq!(
+            "/files/../a.",
+            expand_path("/files/../{a}", Some(&[("a", "a.")].into_iter().collect())).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_serialize_rejects_nested_values() {
+        #[derive(Serialize)]
+        struct Nested {
+            ids: Vec<u32>,
+        }
+
+        assert!(PathParams::from_serialize(&Nested { ids: vec![1] }).is_err());
+    }
+}
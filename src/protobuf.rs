@@ -0,0 +1,247 @@
+use reqwest::Method;
+
+use crate::{
+    ContentType, DeserializationError, Raxios, RaxiosError, RaxiosOptions, RaxiosResponse,
+    RaxiosResult,
+};
+
+impl Raxios {
+    async fn send_protobuf<T, U>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        data: Option<U>,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: prost::Message + Default,
+        U: prost::Message,
+    {
+        let mut options = options.unwrap_or_default();
+        options.accept.get_or_insert(ContentType::Protobuf);
+
+        let body = data.map(|data| (data.encode_to_vec(), ContentType::Protobuf));
+        let response = self
+            .build_raw_request(
+                body,
+                Some(&options),
+                self.client
+                    .request(method, self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+
+        self.response_to_raxios_response_with(
+            response,
+            options.deserialize_body,
+            |raw_body, _, _| {
+                T::decode(raw_body.clone()).map_err(|e| {
+                    RaxiosError::DeserializationError(DeserializationError::Protobuf(e))
+                })
+            },
+        )
+        .await
+    }
+
+    /// Sends an HTTP GET request and decodes the `application/x-protobuf` response into `T`
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use prost::Message;
+    /// use raxios::Raxios;
+    ///
+    /// #[derive(Clone, PartialEq, prost::Message)]
+    /// struct ToReturn {
+    ///     #[prost(string, tag = "1")]
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///     let expected = ToReturn { name: String::from("raxios") };
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/test").method(GET).header("accept", "application/x-protobuf");
+    ///         then.status(200).body(expected.encode_to_vec());
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let response = raxios.get_protobuf::<ToReturn>("/test", None).await.unwrap();
+    ///     assert_eq!(expected, response.body.unwrap());
+    /// }
+    /// ```
+    pub async fn get_protobuf<T>(
+        &self,
+        endpoint: &str,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: prost::Message + Default,
+    {
+        self.send_protobuf::<T, ()>(Method::GET, endpoint, None, options)
+            .await
+    }
+
+    /// Sends an HTTP POST request with an `application/x-protobuf` body and decodes the response into `T`
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `data` - Optional message to send to the remote endpoint. If `None`, then no data is sent
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    pub async fn post_protobuf<T, U>(
+        &self,
+        endpoint: &str,
+        data: Option<U>,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: prost::Message + Default,
+        U: prost::Message,
+    {
+        self.send_protobuf(Method::POST, endpoint, data, options)
+            .await
+    }
+
+    /// Sends an HTTP PUT request with an `application/x-protobuf` body and decodes the response into `T`
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `data` - Optional message to send to the remote endpoint. If `None`, then no data is sent
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    pub async fn put_protobuf<T, U>(
+        &self,
+        endpoint: &str,
+        data: Option<U>,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: prost::Message + Default,
+        U: prost::Message,
+    {
+        self.send_protobuf(Method::PUT, endpoint, data, options)
+            .await
+    }
+
+    /// Sends an HTTP PATCH request with an `application/x-protobuf` body and decodes the response into `T`
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `data` - Optional message to send to the remote endpoint. If `None`, then no data is sent
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    pub async fn patch_protobuf<T, U>(
+        &self,
+        endpoint: &str,
+        data: Option<U>,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: prost::Message + Default,
+        U: prost::Message,
+    {
+        self.send_protobuf(Method::PATCH, endpoint, data, options)
+            .await
+    }
+
+    /// Sends an HTTP DELETE request with an optional `application/x-protobuf` body and decodes the response into `T`
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `data` - Optional message to send to the remote endpoint. If `None`, then no data is sent
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    pub async fn delete_protobuf<T, U>(
+        &self,
+        endpoint: &str,
+        data: Option<U>,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: prost::Message + Default,
+        U: prost::Message,
+    {
+        self.send_protobuf(Method::DELETE, endpoint, data, options)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use prost::Message;
+
+    use crate::{ContentType, Raxios, RaxiosError};
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Order {
+        #[prost(uint64, tag = "1")]
+        id: u64,
+        #[prost(string, tag = "2")]
+        name: String,
+    }
+
+    const REQUEST: &[u8] = &[8, 1, 18, 4, 116, 101, 115, 116];
+
+    #[tokio::test]
+    async fn test_post_protobuf() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+        let request = Order {
+            id: 1,
+            name: String::from("test"),
+        };
+        let expected_response = Order {
+            id: 2,
+            name: String::from("created"),
+        };
+        assert_eq!(REQUEST, request.encode_to_vec());
+
+        let mock = server.mock(|when, then| {
+            when.path("/orders")
+                .method(POST)
+                .query_param("dry_run", "true")
+                .header(
+                    reqwest::header::CONTENT_TYPE.to_string(),
+                    ContentType::Protobuf.to_string(),
+                )
+                .header(
+                    reqwest::header::ACCEPT.to_string(),
+                    ContentType::Protobuf.to_string(),
+                )
+                .matches(|req| req.body.as_deref() == Some(REQUEST));
+            then.status(201).body(expected_response.encode_to_vec());
+        });
+
+        let res = raxios
+            .post_protobuf::<Order, Order>(
+                "/orders",
+                Some(request),
+                Some(crate::RaxiosOptions {
+                    params: Some(crate::map_string! {dry_run : "true"}),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(expected_response, res.body.unwrap());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_network_error() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let raxios = Raxios::new(&server.base_url(), None)?;
+
+        server.mock(|when, then| {
+            when.path("/orders/1").method(GET);
+            then.status(404).body("not found");
+        });
+
+        let res = raxios.get_protobuf::<Order>("/orders/1", None).await;
+        assert!(matches!(res, Err(RaxiosError::NetworkError(ref err)) if err.status_code == 404));
+
+        Ok(())
+    }
+}
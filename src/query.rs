@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::SerializationError;
+
+/// Everything except the unreserved characters of RFC 3986 and `,` is percent-encoded, so that `+`
+/// and spaces can not be confused. `,` is left as is so that `ArrayFormat::Comma` reads naturally
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b',')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Controls how sequences are written when serializing query parameters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayFormat {
+    /// Repeat the key for every item: `id=1&id=2`
+    #[default]
+    Repeat,
+    /// Repeat the key with a `[]` suffix for every item: `id[]=1&id[]=2`
+    Brackets,
+    /// Join the items with commas: `id=1,2`
+    Comma,
+}
+
+/// An ordered list of query parameters. Keys may be repeated, and parameters are sent in the
+/// order they were added.
+///
+/// # Example
+/// ```rust
+/// use raxios::{ArrayFormat, QueryParams};
+///
+/// #[derive(serde::Serialize)]
+/// struct Search {
+///     q: String,
+///     tags: Vec<&'static str>,
+/// }
+///
+/// let query = QueryParams::from_serialize(
+///     &Search { q: String::from("a&b"), tags: vec!["x", "y"] },
+///     ArrayFormat::Brackets,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     vec![("q", "a&b"), ("tags[]", "x"), ("tags[]", "y")],
+///     query.iter().collect::<Vec<_>>()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryParams {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds `key=value`, keeping any parameters already added with the same key
+    pub fn append(&mut self, key: impl Into<String>, value: impl ToString) -> &mut Self {
+        self.pairs.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Sets `key=value`, replacing every parameter already added with the same key
+    pub fn insert(&mut self, key: impl Into<String>, value: impl ToString) -> &mut Self {
+        let key = key.into();
+        self.pairs.retain(|(existing, _)| *existing != key);
+        self.append(key, value)
+    }
+
+    /// Flattens a struct or map into query parameters.
+    ///
+    /// Fields are emitted in key order. `None` fields are skipped, nested structs and maps are
+    /// written as `outer[inner]=value`, and sequences are written according to `format`.
+    /// Sequences which contain structs, maps or other sequences are always written with indices,
+    /// as in `items[0][id]=1`.
+    pub fn from_serialize<T>(value: &T, format: ArrayFormat) -> Result<Self, SerializationError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut query = Self::new();
+        match serde_json::to_value(value).map_err(SerializationError::Json)? {
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    query.flatten(key, value, format);
+                }
+            }
+            Value::Null => {}
+            _ => {
+                return Err(SerializationError::Unknown(String::from(
+                    "Query parameters must serialize to a struct or map",
+                )))
+            }
+        }
+        Ok(query)
+    }
+
+    fn flatten(&mut self, key: String, value: Value, format: ArrayFormat) {
+        match value {
+            Value::Null => {}
+            Value::Object(fields) => {
+                for (inner, value) in fields {
+                    self.flatten(format!("{key}[{inner}]"), value, format);
+                }
+            }
+            Value::Array(items) if items.iter().any(|item| item.is_array() || item.is_object()) => {
+                for (index, value) in items.into_iter().enumerate() {
+                    self.flatten(format!("{key}[{index}]"), value, format);
+                }
+            }
+            Value::Array(items) => match format {
+                ArrayFormat::Repeat => {
+                    for value in items {
+                        self.flatten(key.clone(), value, format);
+                    }
+                }
+                ArrayFormat::Brackets => {
+                    for value in items {
+                        self.flatten(format!("{key}[]"), value, format);
+                    }
+                }
+                ArrayFormat::Comma => {
+                    let joined = items
+                        .into_iter()
+                        .filter_map(scalar_to_string)
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    self.pairs.push((key, joined));
+                }
+            },
+            scalar => {
+                if let Some(value) = scalar_to_string(scalar) {
+                    self.pairs.push((key, value));
+                }
+            }
+        }
+    }
+
+    /// Iterates over the parameters in the order they will be sent
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Percent-encodes the parameters as `key=value` pairs joined by `&`
+    pub fn encode(&self) -> String {
+        self.iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}={}",
+                    utf8_percent_encode(key, QUERY_ENCODE_SET),
+                    utf8_percent_encode(value, QUERY_ENCODE_SET)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+}
+
+fn scalar_to_string(value: Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(string) => Some(string),
+        other => Some(other.to_string()),
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for QueryParams
+where
+    K: Into<String>,
+    V: ToString,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut query = Self::new();
+        for (key, value) in iter {
+            query.append(key, value);
+        }
+        query
+    }
+}
+
+/// Sorts the parameters by key, so that the order they are sent in is stable
+impl From<HashMap<String, String>> for QueryParams {
+    fn from(params: HashMap<String, String>) -> Self {
+        let mut pairs: Vec<_> = params.into_iter().collect();
+        pairs.sort();
+        Self { pairs }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Serialize;
+
+    use super::{ArrayFormat, QueryParams};
+
+    #[derive(Serialize)]
+    struct Filter {
+        status: &'static str,
+        owner: Owner,
+    }
+
+    #[derive(Serialize)]
+    struct Owner {
+        id: u32,
+        name: Option<&'static str>,
+    }
+
+    #[derive(Serialize)]
+    struct Search {
+        ids: Vec<u32>,
+        filter: Filter,
+        page: Option<u32>,
+        exact: bool,
+    }
+
+    fn search() -> Search {
+        Search {
+            ids: vec![1, 2],
+            filter: Filter {
+                status: "open",
+                owner: Owner { id: 7, name: None },
+            },
+            page: None,
+            exact: true,
+        }
+    }
+
+    fn pairs(query: &QueryParams) -> Vec<(&str, &str)> {
+        query.iter().collect()
+    }
+
+    #[test]
+    fn test_from_serialize_array_formats() {
+        let repeat = QueryParams::from_serialize(&search(), ArrayFormat::Repeat).unwrap();
+        assert_eq!(
+            vec![
+                ("exact", "true"),
+                ("filter[owner][id]", "7"),
+                ("filter[status]", "open"),
+                ("ids", "1"),
+                ("ids", "2"),
+            ],
+            pairs(&repeat)
+        );
+
+        let brackets = QueryParams::from_serialize(&search(), ArrayFormat::Brackets).unwrap();
+        assert_eq!(("ids[]", "1"), pairs(&brackets)[3]);
+        assert_eq!(("ids[]", "2"), pairs(&brackets)[4]);
+
+        let comma = QueryParams::from_serialize(&search(), ArrayFormat::Comma).unwrap();
+        assert_eq!(("ids", "1,2"), pairs(&comma)[3]);
+        assert_eq!(4, comma.len());
+    }
+
+    #[test]
+    fn test_from_serialize_indexes_nested_sequences() {
+        #[derive(Serialize)]
+        struct Item {
+            id: u32,
+        }
+        #[derive(Serialize)]
+        struct Order {
+            items: Vec<Item>,
+        }
+
+        let query = QueryParams::from_serialize(
+            &Order {
+                items: vec![Item { id: 1 }, Item { id: 2 }],
+            },
+            ArrayFormat::Comma,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![("items[0][id]", "1"), ("items[1][id]", "2")],
+            pairs(&query)
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        let query: QueryParams = [("q", "a&b=c #d+e"), ("tags[]", "ü"), ("id", "1,2")]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            "q=a%26b%3Dc%20%23d%2Be&tags%5B%5D=%C3%BC&id=1,2",
+            query.encode()
+        );
+    }
+
+    #[test]
+    fn test_from_serialize_rejects_scalars() {
+        assert!(QueryParams::from_serialize(&42, ArrayFormat::Repeat).is_err());
+    }
+
+    #[test]
+    fn test_from_hash_map_is_sorted() {
+        let map: HashMap<String, String> = [("b", "2"), ("c", "3"), ("a", "1")]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        assert_eq!(
+            vec![("a", "1"), ("b", "2"), ("c", "3")],
+            pairs(&QueryParams::from(map))
+        );
+    }
+}
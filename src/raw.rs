@@ -0,0 +1,420 @@
+use std::fmt::Display;
+
+use serde::{
+    de::{self, value::SeqDeserializer, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, ser, Serialize,
+};
+use thiserror::Error;
+
+/// Represents an error that occurs when a value cannot be sent or received verbatim as
+/// `text/plain` or `application/octet-stream`
+#[derive(Error, Debug)]
+#[error("{0}")]
+pub struct RawError(String);
+
+impl ser::Error for RawError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl de::Error for RawError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+fn unsupported<T>(kind: &str) -> Result<T, RawError> {
+    Err(RawError(format!(
+        "{kind} cannot be sent as a raw body. Only strings, bytes and primitives are supported"
+    )))
+}
+
+/// Serializes strings, bytes (including `Vec<u8>`) and primitives into a body verbatim
+pub(crate) fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>, RawError> {
+    let mut output = Vec::new();
+    value.serialize(RawSerializer {
+        output: &mut output,
+        in_seq: false,
+    })?;
+    Ok(output)
+}
+
+/// Decodes `raw` using `charset`, falling back to UTF-8 if the charset is missing or unknown
+pub(crate) fn decode_text(raw: &[u8], charset: Option<&str>) -> String {
+    let encoding = charset
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    encoding.decode(raw).0.into_owned()
+}
+
+struct RawSerializer<'a> {
+    output: &'a mut Vec<u8>,
+    /// Set while serializing the elements of a sequence, where only `u8` is accepted
+    in_seq: bool,
+}
+
+impl<'a> RawSerializer<'a> {
+    fn write_display<T: Display>(self, value: T) -> Result<(), RawError> {
+        if self.in_seq {
+            return unsupported("A sequence of anything other than u8");
+        }
+        self.output.extend_from_slice(value.to_string().as_bytes());
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for RawSerializer<'a> {
+    type Ok = ();
+    type Error = RawError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = ser::Impossible<(), RawError>;
+    type SerializeTupleVariant = ser::Impossible<(), RawError>;
+    type SerializeMap = ser::Impossible<(), RawError>;
+    type SerializeStruct = ser::Impossible<(), RawError>;
+    type SerializeStructVariant = ser::Impossible<(), RawError>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), RawError> {
+        if self.in_seq {
+            self.output.push(v);
+            Ok(())
+        } else {
+            self.write_display(v)
+        }
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), RawError> {
+        self.write_display(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), RawError> {
+        if self.in_seq {
+            return unsupported("A nested sequence");
+        }
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), RawError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), RawError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), RawError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), RawError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), RawError> {
+        self.write_display(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), RawError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), RawError> {
+        unsupported("An enum variant")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, RawError> {
+        if self.in_seq {
+            return unsupported("A nested sequence");
+        }
+        Ok(Self {
+            output: self.output,
+            in_seq: true,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, RawError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, RawError> {
+        unsupported("A tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, RawError> {
+        unsupported("An enum variant")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, RawError> {
+        unsupported("A map")
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, RawError> {
+        unsupported("A struct")
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, RawError> {
+        unsupported("An enum variant")
+    }
+}
+
+impl<'a> ser::SerializeSeq for RawSerializer<'a> {
+    type Ok = ();
+    type Error = RawError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RawError> {
+        value.serialize(RawSerializer {
+            output: self.output,
+            in_seq: true,
+        })
+    }
+
+    fn end(self) -> Result<(), RawError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTuple for RawSerializer<'a> {
+    type Ok = ();
+    type Error = RawError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RawError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), RawError> {
+        Ok(())
+    }
+}
+
+/// Deserializes a body verbatim into strings, bytes (including `Vec<u8>` and `Bytes`) or `()`
+pub(crate) struct RawDeserializer<'a> {
+    raw: &'a [u8],
+    /// `Some` when the body is text, holding the charset it is encoded in
+    charset: Option<Option<&'a str>>,
+}
+
+impl<'a> RawDeserializer<'a> {
+    /// A deserializer for a `text/plain` body encoded with `charset`
+    pub(crate) fn text(raw: &'a [u8], charset: Option<&'a str>) -> Self {
+        Self {
+            raw,
+            charset: Some(charset),
+        }
+    }
+
+    /// A deserializer for an `application/octet-stream` body
+    pub(crate) fn bytes(raw: &'a [u8]) -> Self {
+        Self { raw, charset: None }
+    }
+
+    fn decoded(&self) -> String {
+        decode_text(self.raw, self.charset.flatten())
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for RawDeserializer<'a> {
+    type Error = RawError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        match self.charset {
+            Some(_) => visitor.visit_string(self.decoded()),
+            None => visitor.visit_bytes(self.raw),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        match self.charset {
+            Some(_) => visitor.visit_string(self.decoded()),
+            None => visitor.visit_string(
+                String::from_utf8(self.raw.to_vec())
+                    .map_err(|_| RawError(String::from("Body does not contain valid Utf8")))?,
+            ),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        visitor.visit_bytes(self.raw)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        visitor.visit_byte_buf(self.raw.to_vec())
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        visitor.visit_seq(SeqDeserializer::new(
+            self.raw.iter().map(|byte| byte.into_deserializer()),
+        ))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        if self.raw.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RawError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RawError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RawError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        tuple tuple_struct map struct enum identifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use serde::Deserialize;
+
+    use super::{to_vec, RawDeserializer};
+
+    #[test]
+    fn test_serialize_strings_and_bytes() {
+        assert_eq!(b"hello".to_vec(), to_vec(&"hello").unwrap());
+        assert_eq!(b"hello".to_vec(), to_vec(&String::from("hello")).unwrap());
+        assert_eq!(
+            vec![0u8, 159, 146, 150],
+            to_vec(&vec![0u8, 159, 146, 150]).unwrap()
+        );
+        assert_eq!(
+            b"\x00\xff".to_vec(),
+            to_vec(&Bytes::from_static(b"\x00\xff")).unwrap()
+        );
+        assert_eq!(b"42".to_vec(), to_vec(&42).unwrap());
+    }
+
+    #[test]
+    fn test_serialize_rejects_structured_values() {
+        assert!(to_vec(&vec![String::from("a")]).is_err());
+        assert!(to_vec(&std::collections::HashMap::<String, String>::new()).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_text_with_charset() {
+        let text = String::deserialize(RawDeserializer::text(b"caf\xe9", Some("iso-8859-1")));
+        assert_eq!("café", text.unwrap());
+    }
+
+    #[test]
+    fn test_deserialize_bytes() {
+        let raw = b"\x00\xff\x10";
+        assert_eq!(
+            Bytes::from_static(raw),
+            Bytes::deserialize(RawDeserializer::bytes(raw)).unwrap()
+        );
+        assert_eq!(
+            raw.to_vec(),
+            Vec::<u8>::deserialize(RawDeserializer::bytes(raw)).unwrap()
+        );
+        assert!(String::deserialize(RawDeserializer::bytes(raw)).is_err());
+        <()>::deserialize(RawDeserializer::bytes(raw)).unwrap();
+    }
+}
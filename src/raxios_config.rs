@@ -1,4 +1,8 @@
-use crate::{RaxiosHeaders, raxios_options::ContentType};
+#[cfg(feature = "csv")]
+use crate::raxios_options::CsvOptions;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::{codec::CodecRegistry, raxios_options::ContentType, RaxiosHeaders};
 
 #[derive(Default, Debug, Clone)]
 pub struct RaxiosConfig {
@@ -7,5 +11,71 @@ pub struct RaxiosConfig {
     /// What content-type should these requests accept (overrideable via RaxiosOptions)
     pub accept: ContentType,
     /// What content-type does do these requests send (overrideable via RaxiosOptions)
-    pub content_type: ContentType
+    pub content_type: ContentType,
+    /// How `text/csv` bodies are read and written (overrideable via RaxiosOptions)
+    #[cfg(feature = "csv")]
+    pub csv: CsvOptions,
+    /// The codecs used to encode request bodies and decode response bodies
+    pub codecs: CodecRegistry,
+    /// Credentials sent in the `authorization` header of every request
+    pub auth: Option<RaxiosAuth>,
+    /// Whether concurrent identical GET requests share one network call (overrideable via
+    /// RaxiosOptions). Defaults to `false`
+    pub deduplicate_gets: bool,
+}
+
+/// Credentials for the `authorization` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RaxiosAuth {
+    /// HTTP Basic authentication
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    /// A bearer token, such as an OAuth 2.0 access token
+    Bearer(String),
+}
+
+impl RaxiosAuth {
+    /// The value of the `authorization` header for these credentials
+    pub fn header_value(&self) -> String {
+        match self {
+            RaxiosAuth::Basic { username, password } => {
+                let credentials = format!("{username}:{}", password.as_deref().unwrap_or_default());
+                format!("Basic {}", STANDARD.encode(credentials))
+            }
+            RaxiosAuth::Bearer(token) => format!("Bearer {token}"),
+        }
+    }
+}
+
+/// What a `Raxios` instance derived with `Raxios::create` changes about its parent.
+/// Fields which are `None` are inherited from the parent.
+#[derive(Default, Debug, Clone)]
+pub struct RaxiosOverrides {
+    /// Replaces the base url of the parent
+    pub base_url: Option<String>,
+    pub timeout_ms: Option<u64>,
+    /// Merged over the headers of the parent, replacing any header with the same name
+    pub headers: Option<RaxiosHeaders>,
+    pub auth: Option<RaxiosAuth>,
+    pub accept: Option<ContentType>,
+    pub content_type: Option<ContentType>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RaxiosAuth;
+
+    #[test]
+    fn test_auth_header_value() {
+        let basic = RaxiosAuth::Basic {
+            username: String::from("Aladdin"),
+            password: Some(String::from("open sesame")),
+        };
+        assert_eq!("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==", basic.header_value());
+
+        let bearer = RaxiosAuth::Bearer(String::from("token"));
+        assert_eq!("Bearer token", bearer.header_value());
+    }
 }
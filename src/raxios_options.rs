@@ -1,5 +1,6 @@
-use crate::RaxiosHeaders;
+use crate::{MediaType, PathParams, QueryParams, RaxiosHeaders, UriVariables};
 use std::str::FromStr;
+use std::time::Duration;
 use std::{collections::HashMap, fmt::Display};
 
 #[derive(Debug, Clone)]
@@ -7,8 +8,27 @@ pub struct RaxiosOptions {
     pub headers: Option<RaxiosHeaders>,
     pub accept: Option<ContentType>,
     pub content_type: Option<ContentType>,
+    /// Query parameters, sent in key order after any query already present in the endpoint
     pub params: Option<HashMap<String, String>>,
+    /// Ordered query parameters, sent after `params`. Use `QueryParams::from_serialize` to build
+    /// these from a struct
+    pub query: Option<QueryParams>,
+    /// Values for the `{name}` placeholders of a templated endpoint such as `/users/{id}`. Each
+    /// value is percent-encoded as a single path segment
+    pub path_params: Option<PathParams>,
+    /// When set, the endpoint is expanded as an RFC 6570 URI template (such as `/search{?q,page}`)
+    /// with these variables before `path_params` and the query parameters are applied
+    pub uri_variables: Option<UriVariables>,
     pub deserialize_body: bool,
+    /// Overrides `RaxiosConfig::timeout_ms` for this request
+    pub timeout_ms: Option<u64>,
+    /// How `text/csv` bodies are read and written (overrides RaxiosConfig)
+    #[cfg(feature = "csv")]
+    pub csv: Option<CsvOptions>,
+    /// How `text/event-stream` connections are kept alive
+    pub sse: Option<SseOptions>,
+    /// Overrides `RaxiosConfig::deduplicate_gets` for this request
+    pub deduplicate: Option<bool>,
 }
 
 impl Default for RaxiosOptions {
@@ -16,15 +36,71 @@ impl Default for RaxiosOptions {
         Self {
             headers: Default::default(),
             params: Default::default(),
+            query: Default::default(),
+            path_params: Default::default(),
+            uri_variables: Default::default(),
             accept: Default::default(),
             content_type: Default::default(),
             deserialize_body: true,
+            timeout_ms: Default::default(),
+            #[cfg(feature = "csv")]
+            csv: Default::default(),
+            sse: Default::default(),
+            deduplicate: Default::default(),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+/// Controls how `text/csv` bodies are read and written
+#[cfg(feature = "csv")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The field delimiter. Defaults to `b','`
+    pub delimiter: u8,
+    /// Whether the first row holds the column names. When true, rows are (de)serialized by field
+    /// name, otherwise by position. Defaults to `true`
+    pub has_headers: bool,
+}
+
+#[cfg(feature = "csv")]
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+        }
+    }
+}
+
+/// Controls how Server-Sent Events connections are re-established once they drop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseOptions {
+    /// Whether to reconnect when the connection is closed or fails. Defaults to `true`
+    pub reconnect: bool,
+    /// How long to wait before reconnecting, until the server sends a `retry` hint. Defaults to 3
+    /// seconds
+    pub retry: Duration,
+    /// Give up after this many consecutive failed reconnection attempts. Defaults to `None`, which
+    /// never gives up
+    pub max_reconnects: Option<usize>,
+    /// Sent as `Last-Event-ID` on the first connection, to resume a previous stream
+    pub last_event_id: Option<String>,
+}
+
+impl Default for SseOptions {
+    fn default() -> Self {
+        Self {
+            reconnect: true,
+            retry: Duration::from_secs(3),
+            max_reconnects: None,
+            last_event_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// Suppling Raxios with a ContentType will set the `content-type` header as well as change how the data is serialized to the server
+#[non_exhaustive]
 pub enum ContentType {
     /// Serialize as `application/json`
     Json,
@@ -34,19 +110,88 @@ pub enum ContentType {
     ApplicationXml,
     /// Serialize as `application/x-www-form-urlencoded`
     UrlEncoded,
+    /// Send strings, bytes and primitives verbatim as `text/plain`
+    Text,
+    /// Send bytes verbatim as `application/octet-stream`
+    OctetStream,
+    /// Serialize as `application/x-ndjson`, one JSON value per line. Sequences are sent one item per line
+    NdJson,
+    /// Serialize a sequence of rows as `text/csv`
+    #[cfg(feature = "csv")]
+    Csv,
+    /// Serialize as `application/msgpack`
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+    /// Serialize as `application/cbor`
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// Serialize as `application/yaml`
+    #[cfg(feature = "yaml")]
+    Yaml,
+    /// Serialize as `application/toml`
+    #[cfg(feature = "toml")]
+    Toml,
+    /// Send `prost::Message`s as `application/x-protobuf`. Only usable with the `*_protobuf`
+    /// methods on `Raxios`, since protobuf messages are not (de)serialized with serde
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+    /// Any other media type. Raxios does not know how to (de)serialize these, but they can still
+    /// be sent in the `content-type` and `accept` headers
+    Other(String),
+}
+
+impl From<&MediaType> for ContentType {
+    fn from(media_type: &MediaType) -> Self {
+        match (
+            media_type.type_(),
+            media_type.subtype(),
+            media_type.suffix(),
+        ) {
+            ("application", "json", _) | (_, _, Some("json")) => Self::Json,
+            ("text", "xml", _) => Self::TextXml,
+            ("application", "xml", _) | (_, _, Some("xml")) => Self::ApplicationXml,
+            ("application", "x-www-form-urlencoded", _) => Self::UrlEncoded,
+            ("text", "plain", _) => Self::Text,
+            ("application", "octet-stream", _) => Self::OctetStream,
+            #[cfg(feature = "csv")]
+            ("text" | "application", "csv", _) => Self::Csv,
+            ("application", "x-ndjson" | "ndjson" | "jsonl" | "x-jsonlines" | "json-seq", _) => {
+                Self::NdJson
+            }
+            #[cfg(feature = "msgpack")]
+            ("application", "msgpack" | "x-msgpack", _) => Self::MessagePack,
+            #[cfg(feature = "cbor")]
+            ("application", "cbor", _) | (_, _, Some("cbor")) => Self::Cbor,
+            #[cfg(feature = "yaml")]
+            ("application" | "text", "yaml" | "x-yaml", _) | (_, _, Some("yaml")) => Self::Yaml,
+            #[cfg(feature = "toml")]
+            ("application" | "text", "toml" | "x-toml", _) => Self::Toml,
+            #[cfg(feature = "protobuf")]
+            ("application", "x-protobuf" | "protobuf" | "vnd.google.protobuf", _) => Self::Protobuf,
+            _ => Self::Other(media_type.essence()),
+        }
+    }
 }
 
+/// Parses a `content-type` header value, ignoring any parameters.
+///
+/// Structured syntax suffixes are honored, so `application/hal+json` parses as `Json` and
+/// `application/atom+xml` parses as `ApplicationXml`. Unknown media types parse as `Other`.
+///
+/// # Example
+/// ```rust
+/// use raxios::ContentType;
+///
+/// assert_eq!(Ok(ContentType::Json), "application/json; charset=utf-8".parse());
+/// assert_eq!(Ok(ContentType::Json), "application/vnd.api+json".parse());
+/// assert_eq!(Ok(ContentType::Other(String::from("text/html"))), "text/html".parse());
+/// assert!("not a media type".parse::<ContentType>().is_err());
+/// ```
 impl FromStr for ContentType {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        return match s {
-            "application/json" => Ok(Self::Json),
-            "text/xml" => Ok(Self::TextXml),
-            "application/xml" => Ok(Self::ApplicationXml),
-            "application/x-www-form-urlencoded" => Ok(Self::UrlEncoded),
-            _ => Err(()),
-        };
+        Ok(Self::from(&s.parse::<MediaType>()?))
     }
 }
 
@@ -63,6 +208,22 @@ impl Display for ContentType {
             ContentType::TextXml => write!(f, "text/xml"),
             ContentType::ApplicationXml => write!(f, "application/xml"),
             ContentType::UrlEncoded => write!(f, "application/x-www-form-urlencoded"),
+            ContentType::Text => write!(f, "text/plain"),
+            ContentType::OctetStream => write!(f, "application/octet-stream"),
+            ContentType::NdJson => write!(f, "application/x-ndjson"),
+            #[cfg(feature = "csv")]
+            ContentType::Csv => write!(f, "text/csv"),
+            #[cfg(feature = "msgpack")]
+            ContentType::MessagePack => write!(f, "application/msgpack"),
+            #[cfg(feature = "cbor")]
+            ContentType::Cbor => write!(f, "application/cbor"),
+            #[cfg(feature = "yaml")]
+            ContentType::Yaml => write!(f, "application/yaml"),
+            #[cfg(feature = "toml")]
+            ContentType::Toml => write!(f, "application/toml"),
+            #[cfg(feature = "protobuf")]
+            ContentType::Protobuf => write!(f, "application/x-protobuf"),
+            ContentType::Other(media_type) => write!(f, "{media_type}"),
         }
     }
 }
@@ -103,4 +264,160 @@ mod tests {
             String::from(c_type)
         );
     }
+
+    #[test]
+    fn test_content_type_text_to_string() {
+        let c_type = ContentType::Text;
+        assert_eq!(String::from("text/plain"), String::from(c_type));
+    }
+
+    #[test]
+    fn test_content_type_octet_stream_to_string() {
+        let c_type = ContentType::OctetStream;
+        assert_eq!(
+            String::from("application/octet-stream"),
+            String::from(c_type)
+        );
+    }
+
+    #[test]
+    fn test_content_type_ndjson_from_str() {
+        assert_eq!(
+            Ok(ContentType::NdJson),
+            "application/x-ndjson".parse::<ContentType>()
+        );
+        assert_eq!(
+            Ok(ContentType::NdJson),
+            "application/json-seq".parse::<ContentType>()
+        );
+        assert_eq!(
+            String::from("application/x-ndjson"),
+            String::from(ContentType::NdJson)
+        );
+    }
+
+    #[test]
+    fn test_content_type_other_to_string() {
+        let c_type = ContentType::Other(String::from("text/html; charset=utf-8"));
+        assert_eq!(
+            String::from("text/html; charset=utf-8"),
+            String::from(c_type)
+        );
+    }
+
+    #[test]
+    fn test_content_type_from_str_with_params() {
+        assert_eq!(
+            Ok(ContentType::Json),
+            "application/json; charset=utf-8".parse::<ContentType>()
+        );
+        assert_eq!(
+            Ok(ContentType::TextXml),
+            "Text/XML;charset=\"utf-8\"".parse::<ContentType>()
+        );
+    }
+
+    #[test]
+    fn test_content_type_from_str_with_suffix() {
+        assert_eq!(
+            Ok(ContentType::Json),
+            "application/hal+json".parse::<ContentType>()
+        );
+        assert_eq!(
+            Ok(ContentType::ApplicationXml),
+            "application/soap+xml; charset=utf-8".parse::<ContentType>()
+        );
+    }
+
+    #[test]
+    fn test_content_type_from_str_unknown() {
+        assert_eq!(
+            Ok(ContentType::Other(String::from("text/html"))),
+            "text/html; charset=utf-8".parse::<ContentType>()
+        );
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_content_type_msgpack_from_str() {
+        assert_eq!(
+            Ok(ContentType::MessagePack),
+            "application/msgpack".parse::<ContentType>()
+        );
+        assert_eq!(
+            Ok(ContentType::MessagePack),
+            "application/x-msgpack".parse::<ContentType>()
+        );
+        assert_eq!(
+            String::from("application/msgpack"),
+            String::from(ContentType::MessagePack)
+        );
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_content_type_cbor_from_str() {
+        assert_eq!(
+            Ok(ContentType::Cbor),
+            "application/cbor".parse::<ContentType>()
+        );
+        assert_eq!(
+            String::from("application/cbor"),
+            String::from(ContentType::Cbor)
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_content_type_yaml_from_str() {
+        assert_eq!(
+            Ok(ContentType::Yaml),
+            "application/yaml".parse::<ContentType>()
+        );
+        assert_eq!(Ok(ContentType::Yaml), "text/x-yaml".parse::<ContentType>());
+        assert_eq!(
+            String::from("application/yaml"),
+            String::from(ContentType::Yaml)
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_content_type_toml_from_str() {
+        assert_eq!(
+            Ok(ContentType::Toml),
+            "application/toml; charset=utf-8".parse::<ContentType>()
+        );
+        assert_eq!(
+            String::from("application/toml"),
+            String::from(ContentType::Toml)
+        );
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[test]
+    fn test_content_type_protobuf_from_str() {
+        assert_eq!(
+            Ok(ContentType::Protobuf),
+            "application/x-protobuf".parse::<ContentType>()
+        );
+        assert_eq!(
+            Ok(ContentType::Protobuf),
+            "application/protobuf".parse::<ContentType>()
+        );
+        assert_eq!(
+            String::from("application/x-protobuf"),
+            String::from(ContentType::Protobuf)
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_content_type_csv_from_str() {
+        assert_eq!(
+            Ok(ContentType::Csv),
+            "text/csv; charset=utf-8; header=present".parse::<ContentType>()
+        );
+        assert_eq!(String::from("text/csv"), String::from(ContentType::Csv));
+    }
 }
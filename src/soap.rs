@@ -0,0 +1,643 @@
+use std::fmt::Display;
+
+use reqwest::StatusCode;
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+use xml::{
+    reader::{EventReader, XmlEvent},
+    EmitterConfig,
+};
+
+use crate::{
+    media_type::quote, utils::reqwest_headers_to_map, ContentType, DeserializationError, Raxios,
+    RaxiosError, RaxiosOptions, RaxiosResponse, RaxiosResult, SerializationError,
+};
+
+const SOAP11_ENVELOPE: &str = "http://schemas.xmlsoap.org/soap/envelope/";
+const SOAP12_ENVELOPE: &str = "http://www.w3.org/2003/05/soap-envelope";
+
+/// The version of the SOAP protocol, which decides the envelope namespace, the content-type and
+/// how the action is sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SoapVersion {
+    /// SOAP 1.1: `text/xml`, with the action in the `SOAPAction` header
+    #[default]
+    Soap11,
+    /// SOAP 1.2: `application/soap+xml`, with the action as a parameter of the content-type
+    Soap12,
+}
+
+impl SoapVersion {
+    fn namespace(self) -> &'static str {
+        match self {
+            SoapVersion::Soap11 => SOAP11_ENVELOPE,
+            SoapVersion::Soap12 => SOAP12_ENVELOPE,
+        }
+    }
+
+    fn media_type(self) -> &'static str {
+        match self {
+            SoapVersion::Soap11 => "text/xml",
+            SoapVersion::Soap12 => "application/soap+xml",
+        }
+    }
+}
+
+/// A SOAP call, whose `body` is serialized with `serde-xml-rs` inside of a SOAP envelope
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoapRequest<B> {
+    pub version: SoapVersion,
+    /// The action of the operation, such as `urn:GetPrice`
+    pub action: Option<String>,
+    /// Namespaces declared on the envelope as `(prefix, uri)`, so that prefixed element names in
+    /// the body and header blocks (such as `#[serde(rename = "m:GetPrice")]`) resolve
+    pub namespaces: Vec<(String, String)>,
+    /// The XML of every header block, in order. Use `SoapRequest::add_header` to serialize them
+    pub headers: Vec<String>,
+    pub body: B,
+}
+
+impl<B> SoapRequest<B> {
+    pub fn new(body: B) -> Self {
+        Self {
+            version: Default::default(),
+            action: None,
+            namespaces: Vec::new(),
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    /// Serializes `header` as a header block of the envelope
+    pub fn add_header<H>(&mut self, header: &H) -> Result<&mut Self, SerializationError>
+    where
+        H: Serialize,
+    {
+        self.headers.push(to_xml_fragment(header)?);
+        Ok(self)
+    }
+}
+
+impl<B> SoapRequest<B>
+where
+    B: Serialize,
+{
+    /// The complete SOAP envelope of this request
+    pub fn envelope(&self) -> Result<String, SerializationError> {
+        let namespaces: String = self
+            .namespaces
+            .iter()
+            .map(|(prefix, uri)| format!(" xmlns:{prefix}=\"{}\"", escape_attribute(uri)))
+            .collect();
+        let headers = match self.headers.is_empty() {
+            true => String::new(),
+            false => format!("<soap:Header>{}</soap:Header>", self.headers.concat()),
+        };
+
+        Ok(format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><soap:Envelope xmlns:soap=\"{}\"{namespaces}>{headers}<soap:Body>{}</soap:Body></soap:Envelope>",
+            self.version.namespace(),
+            to_xml_fragment(&self.body)?
+        ))
+    }
+}
+
+/// Serializes `value` without the XML declaration, so that it can be embedded in the envelope
+fn to_xml_fragment<T>(value: &T) -> Result<String, SerializationError>
+where
+    T: Serialize,
+{
+    let xml = serde_xml_rs::to_string(value).map_err(SerializationError::Xml)?;
+    match xml
+        .strip_prefix("<?xml")
+        .and_then(|xml| xml.split_once("?>"))
+    {
+        Some((_, fragment)) => Ok(fragment.to_owned()),
+        None => Ok(xml),
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
+
+/// Represents a SOAP fault, from either a SOAP 1.1 or a SOAP 1.2 service
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub struct SoapFault {
+    pub status_code: StatusCode,
+    /// `faultcode` (1.1) or `Code/Value` (1.2), such as `soap:Server`
+    pub code: String,
+    /// `Code/Subcode/Value` (1.2 only)
+    pub subcode: Option<String>,
+    /// `faultstring` (1.1) or the first `Reason/Text` (1.2)
+    pub reason: String,
+    /// `faultactor` (1.1) or `Role` (1.2)
+    pub actor: Option<String>,
+    /// The XML of the `detail` (1.1) or `Detail` (1.2) element, if present
+    pub detail: Option<String>,
+}
+
+impl Display for SoapFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SOAP Fault -- Status: {}, Code: {}, Reason: {}",
+            self.status_code, self.code, self.reason
+        )
+    }
+}
+
+impl SoapFault {
+    /// Deserializes the children of the detail element into `D`
+    pub fn deserialize_detail<D>(&self) -> RaxiosResult<Option<D>>
+    where
+        D: DeserializeOwned,
+    {
+        self.detail
+            .as_deref()
+            .map(serde_xml_rs::from_str)
+            .transpose()
+            .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Xml(e)))
+    }
+}
+
+fn xml_error(err: impl Into<serde_xml_rs::Error>) -> RaxiosError {
+    RaxiosError::DeserializationError(DeserializationError::Xml(err.into()))
+}
+
+/// Writes `start` and everything up to its matching end element as a standalone XML document.
+/// Namespaces in scope are declared on the root, so prefixes keep resolving.
+fn write_subtree<R>(reader: &mut EventReader<R>, start: XmlEvent) -> RaxiosResult<String>
+where
+    R: std::io::Read,
+{
+    let mut output = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut output);
+
+    let mut depth = 0;
+    let mut event = start;
+    loop {
+        match event {
+            XmlEvent::StartElement { .. } => depth += 1,
+            XmlEvent::EndElement { .. } => depth -= 1,
+            XmlEvent::EndDocument => {
+                return Err(RaxiosError::DeserializationError(
+                    DeserializationError::Unknown(String::from("Unexpected end of SOAP envelope")),
+                ))
+            }
+            _ => {}
+        }
+        if let Some(writer_event) = event.as_writer_event() {
+            writer.write(writer_event).map_err(xml_error)?;
+        }
+        if depth == 0 {
+            break;
+        }
+        event = reader.next().map_err(xml_error)?;
+    }
+
+    String::from_utf8(output).map_err(xml_error)
+}
+
+fn is_envelope_namespace(namespace: Option<&str>) -> bool {
+    matches!(namespace, Some(SOAP11_ENVELOPE | SOAP12_ENVELOPE))
+}
+
+fn parse_fault(xml: &str, status_code: StatusCode) -> RaxiosResult<SoapFault> {
+    let mut fault = SoapFault {
+        status_code,
+        code: String::new(),
+        subcode: None,
+        reason: String::new(),
+        actor: None,
+        detail: None,
+    };
+
+    let mut reader = EventReader::from_str(xml);
+    // The local names of the open elements below `Fault`
+    let mut path: Vec<String> = Vec::new();
+    let mut depth = 0;
+    loop {
+        let event = reader.next().map_err(xml_error)?;
+        match &event {
+            XmlEvent::StartElement { name, .. } => {
+                depth += 1;
+                if depth == 2 && name.local_name.eq_ignore_ascii_case("detail") {
+                    fault.detail = Some(write_subtree(&mut reader, event)?);
+                    depth -= 1;
+                } else if depth > 1 {
+                    path.push(name.local_name.clone());
+                }
+            }
+            XmlEvent::EndElement { .. } => {
+                depth -= 1;
+                path.pop();
+            }
+            XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                let path: Vec<&str> = path.iter().map(String::as_str).collect();
+                match path.as_slice() {
+                    ["faultcode"] | ["Code", "Value"] => fault.code += text.trim(),
+                    ["Code", "Subcode", "Value"] if fault.subcode.is_none() => {
+                        fault.subcode = Some(text.trim().to_owned())
+                    }
+                    ["faultstring"] | ["Reason", "Text"] if fault.reason.is_empty() => {
+                        fault.reason += text.trim()
+                    }
+                    ["faultactor"] | ["Role"] => fault.actor = Some(text.trim().to_owned()),
+                    _ => {}
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(fault)
+}
+
+/// The content of the `Body` of a SOAP envelope
+enum SoapBody {
+    Empty,
+    /// The XML of the first child of `Body`
+    Content(String),
+    Fault(SoapFault),
+}
+
+fn read_body(raw_body: &[u8], status_code: StatusCode) -> RaxiosResult<SoapBody> {
+    let not_an_envelope = || {
+        RaxiosError::DeserializationError(DeserializationError::Unknown(String::from(
+            "Expected a SOAP envelope",
+        )))
+    };
+
+    let mut reader = EventReader::new(raw_body);
+    let mut depth = 0;
+    let mut in_body = false;
+    loop {
+        let event = reader.next().map_err(xml_error)?;
+        match &event {
+            XmlEvent::StartElement { name, .. } => {
+                depth += 1;
+                let is_envelope_element = is_envelope_namespace(name.namespace.as_deref());
+                if depth == 1 && !(is_envelope_element && name.local_name == "Envelope") {
+                    return Err(not_an_envelope());
+                }
+                if depth == 2 && is_envelope_element && name.local_name == "Body" {
+                    in_body = true;
+                }
+                if depth == 3 && in_body {
+                    let is_fault = is_envelope_element && name.local_name == "Fault";
+                    let xml = write_subtree(&mut reader, event)?;
+                    return match is_fault {
+                        true => parse_fault(&xml, status_code).map(SoapBody::Fault),
+                        false => Ok(SoapBody::Content(xml)),
+                    };
+                }
+            }
+            XmlEvent::EndElement { .. } => {
+                depth -= 1;
+                if in_body && depth == 1 {
+                    return Ok(SoapBody::Empty);
+                }
+            }
+            XmlEvent::EndDocument => return Err(not_an_envelope()),
+            _ => {}
+        }
+    }
+}
+
+impl Raxios {
+    /// Sends a SOAP call as an HTTP POST request, and deserializes the first child of the `Body`
+    /// of the response into `T`. The body of the response is `None` when the `Body` is empty.
+    ///
+    /// The action is sent as the `SOAPAction` header for SOAP 1.1, and as the `action` parameter of
+    /// the content-type for SOAP 1.2. A fault is returned as `RaxiosError::SoapFault`, whatever the
+    /// status of the response.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `request` - The body, header blocks and action of the call
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers and/or query params
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use raxios::{Raxios, SoapRequest};
+    ///
+    /// #[derive(serde::Serialize)]
+    /// #[serde(rename = "m:GetPrice")]
+    /// struct GetPrice {
+    ///     item: String,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Debug, PartialEq)]
+    /// struct GetPriceResponse {
+    ///     price: f64,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/stock")
+    ///             .method(POST)
+    ///             .header("soapaction", "\"urn:GetPrice\"")
+    ///             .body_contains("<soap:Body><m:GetPrice><item>Apples</item></m:GetPrice></soap:Body>");
+    ///         then.status(200).header("content-type", "text/xml").body(
+    ///             r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+    ///                  <soap:Body>
+    ///                    <m:GetPriceResponse xmlns:m="urn:stock"><m:price>1.5</m:price></m:GetPriceResponse>
+    ///                  </soap:Body>
+    ///                </soap:Envelope>"#,
+    ///         );
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let request = SoapRequest {
+    ///         action: Some(String::from("urn:GetPrice")),
+    ///         namespaces: vec![(String::from("m"), String::from("urn:stock"))],
+    ///         ..SoapRequest::new(GetPrice { item: String::from("Apples") })
+    ///     };
+    ///     let response = raxios
+    ///         .soap::<GetPriceResponse, _>("/stock", &request, None)
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     assert_eq!(GetPriceResponse { price: 1.5 }, response.body.unwrap());
+    /// }
+    /// ```
+    pub async fn soap<T, B>(
+        &self,
+        endpoint: &str,
+        request: &SoapRequest<B>,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosResponse<T>>
+    where
+        T: DeserializeOwned,
+        B: Serialize,
+    {
+        let mut options = options.unwrap_or_default();
+        let version = request.version;
+        let mut content_type = format!("{}; charset=utf-8", version.media_type());
+        match (version, &request.action) {
+            (SoapVersion::Soap11, action) => {
+                let action = quote(action.as_deref().unwrap_or_default());
+                options
+                    .headers
+                    .get_or_insert_with(Default::default)
+                    .insert("soapaction", action);
+            }
+            (SoapVersion::Soap12, Some(action)) => {
+                content_type += &format!("; action={}", quote(action));
+            }
+            (SoapVersion::Soap12, None) => {}
+        }
+        options
+            .accept
+            .get_or_insert_with(|| ContentType::Other(String::from(version.media_type())));
+
+        let envelope = request
+            .envelope()
+            .map_err(RaxiosError::SerializationError)?;
+        let response = self
+            .build_raw_request(
+                Some((envelope.into_bytes(), ContentType::Other(content_type))),
+                Some(&options),
+                self.client.post(self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+
+        let response = match self.check_response_and_return_err(response).await {
+            Ok(response) => response,
+            // Faults are usually sent with a 500 status
+            Err(RaxiosError::NetworkError(err)) => {
+                let fault = err
+                    .raw_body
+                    .as_ref()
+                    .and_then(|raw_body| read_body(raw_body, err.status_code).ok());
+                return Err(match fault {
+                    Some(SoapBody::Fault(fault)) => RaxiosError::SoapFault(Box::new(fault)),
+                    _ => RaxiosError::NetworkError(err),
+                });
+            }
+            Err(err) => return Err(err),
+        };
+
+        let status = response.status();
+        let remote_address = response.remote_addr();
+        let response_headers = reqwest_headers_to_map(response.headers());
+        let raw_body = response.bytes().await.ok();
+
+        let mut body = None;
+        if let (Some(raw_body), true) = (&raw_body, options.deserialize_body) {
+            match read_body(raw_body, status)? {
+                SoapBody::Fault(fault) => return Err(RaxiosError::SoapFault(Box::new(fault))),
+                SoapBody::Content(xml) => {
+                    body = Some(serde_xml_rs::from_str(&xml).map_err(|e| {
+                        RaxiosError::DeserializationError(DeserializationError::Xml(e))
+                    })?);
+                }
+                SoapBody::Empty => {}
+            }
+        }
+
+        Ok(RaxiosResponse {
+            body,
+            raw_body,
+            status,
+            response_headers,
+            remote_address,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use super::{SoapRequest, SoapVersion};
+    use crate::{ErrorKind, Raxios, RaxiosError};
+
+    #[derive(Serialize)]
+    #[serde(rename = "t:Transaction")]
+    struct Transaction {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename = "m:Charge")]
+    struct Charge {
+        amount: u32,
+    }
+
+    #[test]
+    fn test_envelope_with_header_blocks() {
+        let mut request = SoapRequest {
+            namespaces: vec![
+                (String::from("m"), String::from("urn:pay?a=1&b=2")),
+                (String::from("t"), String::from("urn:tx")),
+            ],
+            ..SoapRequest::new(Charge { amount: 5 })
+        };
+        request.add_header(&Transaction { id: 7 }).unwrap();
+
+        assert_eq!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+                "<soap:Envelope xmlns:soap=\"http://schemas.xmlsoap.org/soap/envelope/\"",
+                " xmlns:m=\"urn:pay?a=1&amp;b=2\" xmlns:t=\"urn:tx\">",
+                "<soap:Header><t:Transaction><id>7</id></t:Transaction></soap:Header>",
+                "<soap:Body><m:Charge><amount>5</amount></m:Charge></soap:Body>",
+                "</soap:Envelope>"
+            ),
+            request.envelope().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_soap12_fault() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Detail {
+            balance: u32,
+        }
+
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/pay")
+                .header(
+                    "content-type",
+                    "application/soap+xml; charset=utf-8; action=\"urn:Charge\"",
+                )
+                .body_contains("xmlns:soap=\"http://www.w3.org/2003/05/soap-envelope\"");
+            then.status(500).body(
+                r#"<?xml version="1.0"?>
+                <env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope">
+                  <env:Body>
+                    <env:Fault>
+                      <env:Code>
+                        <env:Value>env:Sender</env:Value>
+                        <env:Subcode><env:Value>m:InsufficientFunds</env:Value></env:Subcode>
+                      </env:Code>
+                      <env:Reason><env:Text xml:lang="en">Not enough money</env:Text></env:Reason>
+                      <env:Detail><m:balance xmlns:m="urn:pay">3</m:balance></env:Detail>
+                    </env:Fault>
+                  </env:Body>
+                </env:Envelope>"#,
+            );
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let request = SoapRequest {
+            version: SoapVersion::Soap12,
+            action: Some(String::from("urn:Charge")),
+            namespaces: vec![(String::from("m"), String::from("urn:pay"))],
+            ..SoapRequest::new(Charge { amount: 5 })
+        };
+        let err = raxios
+            .soap::<(), _>("/pay", &request, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(ErrorKind::SoapFault, err.kind());
+        assert!(err.is_server_error());
+        let RaxiosError::SoapFault(fault) = err else {
+            panic!("Result was not an instance of SoapFault");
+        };
+        assert_eq!("env:Sender", fault.code);
+        assert_eq!(Some("m:InsufficientFunds"), fault.subcode.as_deref());
+        assert_eq!("Not enough money", fault.reason);
+        assert_eq!(
+            Some(Detail { balance: 3 }),
+            fault.deserialize_detail().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_soap11_fault_on_200_and_empty_body() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/fault");
+            then.status(200).body(
+                r#"<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body><s:Fault>
+                     <faultcode>s:Client</faultcode><faultstring>Bad amount</faultstring>
+                     <faultactor>urn:gateway</faultactor>
+                   </s:Fault></s:Body></s:Envelope>"#,
+            );
+        });
+        server.mock(|when, then| {
+            when.path("/empty");
+            then.status(200).body(
+                r#"<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body/></s:Envelope>"#,
+            );
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let request = SoapRequest::new(Charge { amount: 0 });
+
+        let fault = raxios.soap::<(), _>("/fault", &request, None).await;
+        assert!(matches!(
+            fault,
+            Err(RaxiosError::SoapFault(ref fault))
+                if fault.code == "s:Client"
+                    && fault.reason == "Bad amount"
+                    && fault.actor.as_deref() == Some("urn:gateway")
+        ));
+
+        let empty = raxios
+            .soap::<(), _>("/empty", &request, None)
+            .await
+            .unwrap();
+        assert_eq!(None, empty.body);
+    }
+
+    #[tokio::test]
+    async fn test_actions_are_escaped() {
+        let server = MockServer::start();
+        let soap11 = server.mock(|when, then| {
+            when.path("/soap11")
+                .header("soapaction", r#""urn:a\"b\\c""#);
+            then.status(200).body(
+                r#"<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body/></s:Envelope>"#,
+            );
+        });
+        let soap12 = server.mock(|when, then| {
+            when.path("/soap12").header(
+                "content-type",
+                r#"application/soap+xml; charset=utf-8; action="urn:a\"; x=\"b""#,
+            );
+            then.status(200).body(
+                r#"<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope"><env:Body/></env:Envelope>"#,
+            );
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let request = SoapRequest {
+            action: Some(String::from(r#"urn:a"b\c"#)),
+            ..SoapRequest::new(Charge { amount: 1 })
+        };
+        raxios
+            .soap::<(), _>("/soap11", &request, None)
+            .await
+            .unwrap();
+
+        let request = SoapRequest {
+            version: SoapVersion::Soap12,
+            action: Some(String::from(r#"urn:a"; x="b"#)),
+            ..SoapRequest::new(Charge { amount: 1 })
+        };
+        raxios
+            .soap::<(), _>("/soap12", &request, None)
+            .await
+            .unwrap();
+
+        soap11.assert();
+        soap12.assert();
+    }
+}
@@ -0,0 +1,506 @@
+use std::{pin::Pin, str::FromStr, time::Duration};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    ContentType, DeserializationError, MediaType, Raxios, RaxiosError, RaxiosOptions, RaxiosResult,
+    RaxiosStream, SseOptions,
+};
+
+const EVENT_STREAM: &str = "text/event-stream";
+
+/// A single Server-Sent Event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent<T = String> {
+    /// The last event id sent by the server, which is also sent as `Last-Event-ID` when
+    /// reconnecting
+    pub id: Option<String>,
+    /// The event type. Defaults to `message`
+    pub event: String,
+    /// The data of the event. Multiple `data` lines are joined with `\n`
+    pub data: T,
+    /// The reconnection time the server asked for alongside this event, if any
+    pub retry: Option<Duration>,
+}
+
+impl SseEvent {
+    /// Deserializes `data` as JSON
+    pub fn deserialize_data<T>(self) -> RaxiosResult<SseEvent<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let data = serde_json::from_str(&self.data)
+            .map_err(|e| RaxiosError::DeserializationError(DeserializationError::Json(e)))?;
+        Ok(SseEvent {
+            id: self.id,
+            event: self.event,
+            data,
+            retry: self.retry,
+        })
+    }
+}
+
+/// Incrementally parses a `text/event-stream` body as bytes arrive
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: BytesMut,
+    /// Set after a line ending in `\r`, so that the `\n` of a `\r\n` split across chunks is skipped
+    skip_lf: bool,
+    started: bool,
+    event: Option<String>,
+    data: String,
+    retry: Option<Duration>,
+    last_event_id: Option<String>,
+    /// The latest `retry` hint of the server, which outlives the connection
+    reconnection_time: Option<Duration>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Discards everything of the current connection except the last event id and reconnection time
+    fn reset(&mut self) {
+        *self = Self {
+            last_event_id: self.last_event_id.take(),
+            reconnection_time: self.reconnection_time,
+            ..Default::default()
+        };
+    }
+
+    fn next_line(&mut self) -> Option<BytesMut> {
+        if self.skip_lf && !self.buffer.is_empty() {
+            if self.buffer[0] == b'\n' {
+                self.buffer.advance(1);
+            }
+            self.skip_lf = false;
+        }
+
+        let index = self
+            .buffer
+            .iter()
+            .position(|byte| *byte == b'\n' || *byte == b'\r')?;
+        let mut line = self.buffer.split_to(index + 1);
+        self.skip_lf = line[index] == b'\r';
+        line.truncate(index);
+        Some(line)
+    }
+
+    fn next_event(&mut self) -> Option<SseEvent> {
+        while let Some(line) = self.next_line() {
+            if let Some(event) = self.process_line(&line) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<SseEvent> {
+        let line = String::from_utf8_lossy(line);
+        let line = match self.started {
+            true => &line[..],
+            false => line.trim_start_matches('\u{feff}'),
+        };
+        self.started = true;
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_owned()),
+            "data" => {
+                self.data += value;
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_owned()),
+            "retry" if !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                    self.reconnection_time = self.retry;
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        let mut data = std::mem::take(&mut self.data);
+        if data.is_empty() {
+            return None;
+        }
+        data.pop();
+
+        Some(SseEvent {
+            id: self
+                .last_event_id
+                .clone()
+                .filter(|last_event_id| !last_event_id.is_empty()),
+            event: event
+                .filter(|event| !event.is_empty())
+                .unwrap_or_else(|| String::from("message")),
+            data,
+            retry: self.retry.take(),
+        })
+    }
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+struct SseState {
+    raxios: Raxios,
+    endpoint: String,
+    options: RaxiosOptions,
+    sse: SseOptions,
+    body: Option<ByteStream>,
+    parser: SseParser,
+    /// Cleared once the server or an error ends the stream
+    reconnect: bool,
+    /// The number of consecutive failed reconnection attempts
+    failures: usize,
+}
+
+impl SseState {
+    fn can_retry(&self) -> bool {
+        match self.sse.max_reconnects {
+            Some(max_reconnects) => self.failures + 1 < max_reconnects,
+            None => true,
+        }
+    }
+}
+
+impl Raxios {
+    /// Connects to `endpoint`, or returns `None` if the server answers with `204 No Content`,
+    /// which asks the client not to reconnect
+    async fn connect_sse(
+        &self,
+        endpoint: &str,
+        options: &RaxiosOptions,
+        last_event_id: Option<&str>,
+    ) -> RaxiosResult<Option<Response>> {
+        let mut options = options.clone();
+        let headers = options.headers.get_or_insert_with(Default::default);
+        headers.insert("cache-control", "no-cache");
+        if let Some(last_event_id) = last_event_id.filter(|id| !id.is_empty()) {
+            headers.insert("last-event-id", last_event_id);
+        }
+
+        let response = self
+            .build_raw_request::<Vec<u8>>(
+                None,
+                Some(&options),
+                self.client.get(self.build_url(endpoint, Some(&options))?),
+            )?
+            .send()
+            .await
+            .map_err(|e| RaxiosError::UnableToSendRequest { err: e })?;
+        let response = self.check_response_and_return_err(response).await?;
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        match MediaType::from_str(content_type) {
+            Ok(media_type) if media_type.essence().eq_ignore_ascii_case(EVENT_STREAM) => {
+                Ok(Some(response))
+            }
+            _ => Err(RaxiosError::DeserializationError(
+                DeserializationError::UnsupportedContentType(content_type.to_owned()),
+            )),
+        }
+    }
+
+    /// Opens a Server-Sent Events (`text/event-stream`) connection and streams its events.
+    ///
+    /// The connection uses the base url, default headers and auth of this instance. When it is
+    /// closed or fails, it is re-established after the server's latest `retry` hint (or
+    /// `SseOptions::retry`), sending the id of the last event as `Last-Event-ID`. The stream ends
+    /// when the server answers a reconnection with `204 No Content`, and yields an `Err` and ends
+    /// on an error status, an unexpected content type, or once `SseOptions::max_reconnects`
+    /// consecutive reconnections have failed.
+    ///
+    /// Note that `timeout_ms` applies to the whole connection, so a timeout causes a reconnection.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers, query params and `SseOptions`
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures_util::StreamExt;
+    /// use httpmock::prelude::*;
+    /// use raxios::{Raxios, RaxiosOptions, SseOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start();
+    ///
+    ///     server.mock(|when, then| {
+    ///         when.path("/updates").method(GET);
+    ///         then.status(200)
+    ///             .header("content-type", "text/event-stream")
+    ///             .body("id: 1\nevent: price\ndata: {\"amount\": 42}\n\n");
+    ///     });
+    ///
+    ///     let raxios = Raxios::new(&server.base_url(), None).unwrap();
+    ///     let options = RaxiosOptions {
+    ///         sse: Some(SseOptions {
+    ///             reconnect: false,
+    ///             ..Default::default()
+    ///         }),
+    ///         ..Default::default()
+    ///     };
+    ///     let mut events = raxios.sse("/updates", Some(options)).await.unwrap();
+    ///
+    ///     let event = events.next().await.unwrap().unwrap();
+    ///     assert_eq!(Some("1"), event.id.as_deref());
+    ///     assert_eq!("price", event.event);
+    ///     assert_eq!("{\"amount\": 42}", event.data);
+    ///     assert!(events.next().await.is_none());
+    /// }
+    /// ```
+    pub async fn sse(
+        &self,
+        endpoint: &str,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosStream<SseEvent>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .accept
+            .get_or_insert_with(|| ContentType::Other(String::from(EVENT_STREAM)));
+        let sse = options.sse.clone().unwrap_or_default();
+
+        let response = self
+            .connect_sse(endpoint, &options, sse.last_event_id.as_deref())
+            .await?;
+        let state = SseState {
+            raxios: self.clone(),
+            endpoint: endpoint.to_owned(),
+            parser: SseParser {
+                last_event_id: sse.last_event_id.clone(),
+                ..Default::default()
+            },
+            reconnect: sse.reconnect && response.is_some(),
+            body: response.map(|response| Box::pin(response.bytes_stream()) as ByteStream),
+            options,
+            sse,
+            failures: 0,
+        };
+
+        Ok(Box::pin(stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(body) = &mut state.body {
+                    if let Some(event) = state.parser.next_event() {
+                        return Some((Ok(event), state));
+                    }
+                    match body.next().await {
+                        Some(Ok(chunk)) => {
+                            state.parser.feed(&chunk);
+                            continue;
+                        }
+                        Some(Err(_)) | None => {
+                            state.body = None;
+                            state.parser.reset();
+                        }
+                    }
+                }
+                if !state.reconnect {
+                    return None;
+                }
+
+                tokio::time::sleep(state.parser.reconnection_time.unwrap_or(state.sse.retry)).await;
+                let response = state
+                    .raxios
+                    .connect_sse(
+                        &state.endpoint,
+                        &state.options,
+                        state.parser.last_event_id.as_deref(),
+                    )
+                    .await;
+                match response {
+                    Ok(Some(response)) => {
+                        state.failures = 0;
+                        state.body = Some(Box::pin(response.bytes_stream()));
+                    }
+                    Ok(None) => return None,
+                    Err(RaxiosError::UnableToSendRequest { .. }) if state.can_retry() => {
+                        state.failures += 1;
+                    }
+                    Err(err) => {
+                        state.reconnect = false;
+                        return Some((Err(err), state));
+                    }
+                }
+            }
+        })))
+    }
+
+    /// Like `sse`, but deserializes the data of every event as JSON. An event which fails to
+    /// deserialize yields an `Err` without ending the stream.
+    ///
+    /// * `endpoint` - The remote endpoint. This gets joined with the base_url configured in the ::new() method
+    /// * `options` - The `RaxiosOptions` for this call. Allows setting of headers, query params and `SseOptions`
+    pub async fn sse_json<T>(
+        &self,
+        endpoint: &str,
+        options: Option<RaxiosOptions>,
+    ) -> RaxiosResult<RaxiosStream<SseEvent<T>>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let events = self.sse(endpoint, options).await?;
+        Ok(Box::pin(
+            events.map(|event| event.and_then(SseEvent::deserialize_data)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+    use httpmock::prelude::*;
+
+    use super::{SseEvent, SseParser};
+    use crate::{Raxios, RaxiosOptions, SseOptions};
+
+    fn parse(chunks: &[&str]) -> Vec<SseEvent> {
+        let mut parser = SseParser::default();
+        let mut events = Vec::new();
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes());
+            while let Some(event) = parser.next_event() {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_parse_fields_across_chunks() {
+        let events = parse(&[
+            "\u{feff}: keep-alive\r",
+            "\nid: 7\r\nevent: update\ndata: first\rdata:second\n",
+            "retry: 250\nunknown: field\n\n",
+            "data: {\"n\": 1}\n\nevent: empty\n\n",
+            "data: incomplete",
+        ]);
+
+        assert_eq!(
+            vec![
+                SseEvent {
+                    id: Some(String::from("7")),
+                    event: String::from("update"),
+                    data: String::from("first\nsecond"),
+                    retry: Some(Duration::from_millis(250)),
+                },
+                SseEvent {
+                    id: Some(String::from("7")),
+                    event: String::from("message"),
+                    data: String::from("{\"n\": 1}"),
+                    retry: None,
+                },
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn test_empty_id_clears_last_event_id() {
+        let events = parse(&["id: 1\ndata: a\n\nid\ndata: b\n\n"]);
+        assert_eq!(Some("1"), events[0].id.as_deref());
+        assert_eq!(None, events[1].id);
+    }
+
+    #[test]
+    fn test_deserialize_data() {
+        let event = parse(&["data: [1, 2]\n\n"]).remove(0);
+        assert_eq!(
+            vec![1, 2],
+            event.deserialize_data::<Vec<u32>>().unwrap().data
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sse_reconnects_with_last_event_id() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.path("/events")
+                .header("accept", "text/event-stream")
+                .matches(|req| {
+                    !req.headers
+                        .iter()
+                        .flatten()
+                        .any(|(name, _)| name.eq_ignore_ascii_case("last-event-id"))
+                });
+            then.status(200)
+                .header("content-type", "text/event-stream")
+                .body("retry: 10\nid: 1\ndata: {\"n\": 1}\n\n");
+        });
+        let second = server.mock(|when, then| {
+            when.path("/events").header("last-event-id", "1");
+            then.status(200)
+                .header("content-type", "text/event-stream; charset=utf-8")
+                .body("id: 2\ndata: {\"n\": 2}\n\n");
+        });
+        let done = server.mock(|when, then| {
+            when.path("/events").header("last-event-id", "2");
+            then.status(204);
+        });
+
+        #[derive(serde::Deserialize)]
+        struct Count {
+            n: u32,
+        }
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let counts = raxios
+            .sse_json::<Count>("/events", None)
+            .await
+            .unwrap()
+            .map(|event| event.unwrap().data.n)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(vec![1, 2], counts);
+        first.assert();
+        second.assert();
+        done.assert();
+    }
+
+    #[tokio::test]
+    async fn test_sse_rejects_other_content_types() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/events");
+            then.status(200).json_body(serde_json::json!({}));
+        });
+
+        let raxios = Raxios::new(&server.base_url(), None).unwrap();
+        let options = RaxiosOptions {
+            sse: Some(SseOptions {
+                reconnect: false,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(raxios.sse("/events", Some(options)).await.is_err());
+    }
+}
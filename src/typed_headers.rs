@@ -0,0 +1,716 @@
+use std::time::{Duration, SystemTime};
+
+use percent_encoding::percent_decode_str;
+
+use crate::{
+    media_type::{is_token, split_params, split_unquoted, unquote},
+    NetworkError, RaxiosError, RaxiosHeaders, RaxiosResponse, RaxiosResult,
+};
+
+/// A header which can be parsed from its value
+///
+/// # Example
+/// ```rust
+/// use raxios::{RaxiosHeaders, TypedHeader, TypedHeaders};
+///
+/// struct RequestId(String);
+///
+/// impl TypedHeader for RequestId {
+///     const NAME: &'static str = "x-request-id";
+///
+///     fn parse(value: &str) -> Option<Self> {
+///         Some(Self(value.to_owned()))
+///     }
+/// }
+///
+/// let headers: RaxiosHeaders = [("X-Request-Id", "abc")].into_iter().collect();
+/// assert_eq!("abc", headers.typed_header::<RequestId>().unwrap().unwrap().0);
+/// ```
+pub trait TypedHeader: Sized {
+    /// The name of the header
+    const NAME: &'static str;
+
+    /// Parses the header, returning `None` if `value` is malformed. A header which appears more
+    /// than once is parsed from all of its values joined with `, `.
+    fn parse(value: &str) -> Option<Self>;
+}
+
+/// The size of the body in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentLength(pub u64);
+
+impl TypedHeader for ContentLength {
+    const NAME: &'static str = "content-length";
+
+    /// A list of identical values, such as `10, 10`, is accepted as that value
+    fn parse(value: &str) -> Option<Self> {
+        let mut lengths = value.split(',').map(|value| {
+            let value = value.trim();
+            if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            value.parse::<u64>().ok()
+        });
+        let length = lengths.next()??;
+        for other in lengths {
+            if other? != length {
+                return None;
+            }
+        }
+        Some(Self(length))
+    }
+}
+
+/// An entity tag, as sent in the `etag` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityTag {
+    /// Whether this is a weak validator (`W/"..."`)
+    pub weak: bool,
+    /// The opaque tag, without quotes
+    pub tag: String,
+}
+
+impl TypedHeader for EntityTag {
+    const NAME: &'static str = "etag";
+
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (weak, quoted) = match value.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, value),
+        };
+        let tag = quoted.strip_prefix('"')?.strip_suffix('"')?;
+        if tag.contains('"') {
+            return None;
+        }
+        Some(Self {
+            weak,
+            tag: tag.to_owned(),
+        })
+    }
+}
+
+/// When the resource was last modified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastModified(pub SystemTime);
+
+impl TypedHeader for LastModified {
+    const NAME: &'static str = "last-modified";
+
+    fn parse(value: &str) -> Option<Self> {
+        httpdate::parse_http_date(value.trim()).ok().map(Self)
+    }
+}
+
+/// The directives of the `cache-control` header, such as `max-age=60` or `no-store`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheControl {
+    directives: Vec<(String, Option<String>)>,
+}
+
+impl CacheControl {
+    /// Whether `name` is present. Names are compared ignoring case.
+    pub fn contains(&self, name: &str) -> bool {
+        self.directives
+            .iter()
+            .any(|(directive, _)| directive.eq_ignore_ascii_case(name))
+    }
+
+    /// The argument of `name`, if it is present and has one
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.directives
+            .iter()
+            .find(|(directive, _)| directive.eq_ignore_ascii_case(name))
+            .and_then(|(_, argument)| argument.as_deref())
+    }
+
+    /// Every directive and its argument, in order. Names are lowercase.
+    pub fn directives(&self) -> &[(String, Option<String>)] {
+        &self.directives
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.seconds("max-age")
+    }
+
+    pub fn s_maxage(&self) -> Option<Duration> {
+        self.seconds("s-maxage")
+    }
+
+    pub fn no_cache(&self) -> bool {
+        self.contains("no-cache")
+    }
+
+    pub fn no_store(&self) -> bool {
+        self.contains("no-store")
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.contains("private")
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.contains("public")
+    }
+
+    pub fn must_revalidate(&self) -> bool {
+        self.contains("must-revalidate")
+    }
+
+    fn seconds(&self, name: &str) -> Option<Duration> {
+        self.get(name)?.parse().ok().map(Duration::from_secs)
+    }
+}
+
+impl TypedHeader for CacheControl {
+    const NAME: &'static str = "cache-control";
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut directives = Vec::new();
+        for directive in split_unquoted(value, ',').into_iter().map(str::trim) {
+            if directive.is_empty() {
+                continue;
+            }
+            let (name, argument) = match directive.split_once('=') {
+                Some((name, argument)) => (name.trim(), Some(unquote(argument.trim())?)),
+                None => (directive, None),
+            };
+            if !is_token(name) {
+                return None;
+            }
+            directives.push((name.to_ascii_lowercase(), argument));
+        }
+        Some(Self { directives })
+    }
+}
+
+/// The `content-disposition` header, such as `attachment; filename="report.csv"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDisposition {
+    /// The disposition type, such as `inline` or `attachment`, in lowercase
+    pub disposition: String,
+    /// The suggested filename. The RFC 8187 `filename*` parameter is preferred over `filename`,
+    /// unless it uses a charset other than UTF-8 or ISO-8859-1
+    pub filename: Option<String>,
+}
+
+impl ContentDisposition {
+    pub fn is_attachment(&self) -> bool {
+        self.disposition == "attachment"
+    }
+}
+
+/// Decodes an RFC 8187 extended value, such as `UTF-8''%e2%82%ac%20rates.pdf`
+fn decode_extended_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let encoded = parts.next()?;
+
+    let bytes: Vec<u8> = percent_decode_str(encoded).collect();
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+impl TypedHeader for ContentDisposition {
+    const NAME: &'static str = "content-disposition";
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut parts = split_params(value).into_iter();
+        let disposition = parts.next()?.trim();
+        if !is_token(disposition) {
+            return None;
+        }
+
+        let mut filename = None;
+        let mut extended_filename = None;
+        for param in parts.map(str::trim).filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=')?;
+            match key.trim().to_ascii_lowercase().as_str() {
+                "filename" => filename = Some(unquote(value.trim())?),
+                "filename*" => extended_filename = decode_extended_value(value.trim()),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            disposition: disposition.to_ascii_lowercase(),
+            filename: extended_filename.or(filename),
+        })
+    }
+}
+
+/// Where a redirect or a newly created resource is, as sent by the server. This may be relative
+/// to the url of the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location(pub String);
+
+impl TypedHeader for Location {
+    const NAME: &'static str = "location";
+
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        (!value.is_empty()).then(|| Self(value.to_owned()))
+    }
+}
+
+/// A single link of an RFC 8288 `link` header, such as `<https://api.test/users?page=2>; rel="next"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// The target of the link, as sent. This may be relative to the url of the request.
+    pub target: String,
+    /// The parameters of the link, in order. Names are lowercase.
+    pub params: Vec<(String, String)>,
+}
+
+impl Link {
+    /// The value of the parameter `name`, if present
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the `rel` parameter contains `rel`. Relation types are compared ignoring case.
+    pub fn has_rel(&self, rel: &str) -> bool {
+        self.param("rel")
+            .map(|rels| {
+                rels.split_ascii_whitespace()
+                    .any(|candidate| candidate.eq_ignore_ascii_case(rel))
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Every link of the `link` header, in order
+///
+/// # Example
+/// ```rust
+/// use raxios::{Link, Links, RaxiosHeaders, TypedHeaders};
+///
+/// let headers: RaxiosHeaders = [(
+///     "link",
+///     "</users?page=2>; rel=\"next\", </users?page=9>; rel=\"last\"",
+/// )]
+/// .into_iter()
+/// .collect();
+///
+/// let Links(links) = headers.typed_header::<Links>().unwrap().unwrap();
+/// assert_eq!(Some("last"), links[1].param("rel"));
+///
+/// let next: Link = headers.link("next").unwrap().unwrap();
+/// assert_eq!("/users?page=2", next.target);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Links(pub Vec<Link>);
+
+/// Splits a quoted string from the start of `s`, which must begin with the opening quote
+fn split_quoted(s: &str) -> Option<(String, &str)> {
+    let mut unquoted = String::new();
+    let mut chars = s.strip_prefix('"')?.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => unquoted.push(chars.next()?.1),
+            '"' => return Some((unquoted, &s[index + 2..])),
+            c => unquoted.push(c),
+        }
+    }
+    None
+}
+
+impl TypedHeader for Links {
+    const NAME: &'static str = "link";
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut links = Vec::new();
+        let mut rest = value.trim_start();
+        while !rest.is_empty() {
+            if let Some(remaining) = rest.strip_prefix(',') {
+                rest = remaining.trim_start();
+                continue;
+            }
+
+            let (target, remaining) = rest.strip_prefix('<')?.split_once('>')?;
+            rest = remaining.trim_start();
+
+            let mut params = Vec::new();
+            while let Some(param) = rest.strip_prefix(';') {
+                let param = param.trim_start();
+                let name_end = param
+                    .find(|c: char| c == '=' || c == ';' || c == ',' || c.is_whitespace())
+                    .unwrap_or(param.len());
+                let name = &param[..name_end];
+                if !is_token(name) {
+                    return None;
+                }
+                rest = param[name_end..].trim_start();
+
+                let mut value = String::new();
+                if let Some(remaining) = rest.strip_prefix('=') {
+                    let remaining = remaining.trim_start();
+                    if remaining.starts_with('"') {
+                        let (unquoted, remaining) = split_quoted(remaining)?;
+                        value = unquoted;
+                        rest = remaining;
+                    } else {
+                        let value_end = remaining
+                            .find(|c: char| c == ';' || c == ',' || c.is_whitespace())
+                            .unwrap_or(remaining.len());
+                        value = remaining[..value_end].to_owned();
+                        rest = &remaining[value_end..];
+                    }
+                    rest = rest.trim_start();
+                }
+                params.push((name.to_ascii_lowercase(), value));
+            }
+
+            if !rest.is_empty() && !rest.starts_with(',') {
+                return None;
+            }
+            links.push(Link {
+                target: target.trim().to_owned(),
+                params,
+            });
+        }
+        Some(Self(links))
+    }
+}
+
+/// Typed accessors for the headers of a response.
+///
+/// `RaxiosResponse` and `NetworkError` also have these accessors as inherent methods, so the trait
+/// only needs to be imported to read a `RaxiosHeaders` or to be generic over all three.
+///
+/// Every accessor returns `Ok(None)` when the header is absent, and
+/// `Err(RaxiosError::HeaderParseError)` when it is present but malformed.
+pub trait TypedHeaders {
+    /// The headers to read from
+    fn headers(&self) -> &RaxiosHeaders;
+
+    /// Reads and parses the header `H`
+    fn typed_header<H>(&self) -> RaxiosResult<Option<H>>
+    where
+        H: TypedHeader,
+    {
+        let headers = self.headers();
+        if !headers.contains_key(H::NAME) {
+            return Ok(None);
+        }
+
+        let values = headers
+            .get_all_bytes(H::NAME)
+            .map(|value| std::str::from_utf8(value).ok())
+            .collect::<Option<Vec<_>>>();
+        values
+            .and_then(|values| H::parse(&values.join(", ")))
+            .map(Some)
+            .ok_or_else(|| {
+                let values = headers
+                    .get_all_bytes(H::NAME)
+                    .map(String::from_utf8_lossy)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                RaxiosError::HeaderParseError(H::NAME.to_owned(), values)
+            })
+    }
+
+    fn content_length(&self) -> RaxiosResult<Option<u64>> {
+        Ok(self
+            .typed_header::<ContentLength>()?
+            .map(|ContentLength(length)| length))
+    }
+
+    fn etag(&self) -> RaxiosResult<Option<EntityTag>> {
+        self.typed_header()
+    }
+
+    fn last_modified(&self) -> RaxiosResult<Option<SystemTime>> {
+        Ok(self
+            .typed_header::<LastModified>()?
+            .map(|LastModified(time)| time))
+    }
+
+    fn cache_control(&self) -> RaxiosResult<Option<CacheControl>> {
+        self.typed_header()
+    }
+
+    fn content_disposition(&self) -> RaxiosResult<Option<ContentDisposition>> {
+        self.typed_header()
+    }
+
+    fn location(&self) -> RaxiosResult<Option<String>> {
+        Ok(self
+            .typed_header::<Location>()?
+            .map(|Location(location)| location))
+    }
+
+    /// Every link of the `link` header, or an empty list when it is absent
+    fn links(&self) -> RaxiosResult<Vec<Link>> {
+        Ok(self
+            .typed_header::<Links>()?
+            .map(|Links(links)| links)
+            .unwrap_or_default())
+    }
+
+    /// The first link whose `rel` parameter contains `rel`, such as `next` or `last`
+    fn link(&self, rel: &str) -> RaxiosResult<Option<Link>> {
+        Ok(self.links()?.into_iter().find(|link| link.has_rel(rel)))
+    }
+}
+
+impl TypedHeaders for RaxiosHeaders {
+    fn headers(&self) -> &RaxiosHeaders {
+        self
+    }
+}
+
+impl<T> TypedHeaders for RaxiosResponse<T> {
+    fn headers(&self) -> &RaxiosHeaders {
+        &self.response_headers
+    }
+}
+
+impl TypedHeaders for NetworkError {
+    fn headers(&self) -> &RaxiosHeaders {
+        &self.response_headers
+    }
+}
+
+/// Adds the accessors of `TypedHeaders` to a type as inherent methods, so they can be called
+/// without importing the trait
+macro_rules! inherent_typed_headers {
+    (impl $(<$generic:ident>)? for $type:ty) => {
+        impl $(<$generic>)? $type {
+            /// Reads and parses the header `H`. See `TypedHeaders::typed_header`
+            pub fn typed_header<H>(&self) -> RaxiosResult<Option<H>>
+            where
+                H: TypedHeader,
+            {
+                TypedHeaders::typed_header(self)
+            }
+
+            pub fn content_length(&self) -> RaxiosResult<Option<u64>> {
+                TypedHeaders::content_length(self)
+            }
+
+            pub fn etag(&self) -> RaxiosResult<Option<EntityTag>> {
+                TypedHeaders::etag(self)
+            }
+
+            pub fn last_modified(&self) -> RaxiosResult<Option<SystemTime>> {
+                TypedHeaders::last_modified(self)
+            }
+
+            pub fn cache_control(&self) -> RaxiosResult<Option<CacheControl>> {
+                TypedHeaders::cache_control(self)
+            }
+
+            pub fn content_disposition(&self) -> RaxiosResult<Option<ContentDisposition>> {
+                TypedHeaders::content_disposition(self)
+            }
+
+            pub fn location(&self) -> RaxiosResult<Option<String>> {
+                TypedHeaders::location(self)
+            }
+
+            /// Every link of the `link` header, or an empty list when it is absent
+            pub fn links(&self) -> RaxiosResult<Vec<Link>> {
+                TypedHeaders::links(self)
+            }
+
+            /// The first link whose `rel` parameter contains `rel`, such as `next` or `last`
+            pub fn link(&self, rel: &str) -> RaxiosResult<Option<Link>> {
+                TypedHeaders::link(self, rel)
+            }
+        }
+    };
+}
+
+inherent_typed_headers!(impl<T> for RaxiosResponse<T>);
+inherent_typed_headers!(impl for NetworkError);
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{ContentDisposition, EntityTag, Link, TypedHeaders};
+    use crate::{RaxiosError, RaxiosHeaders};
+
+    fn headers(pairs: &[(&str, &str)]) -> RaxiosHeaders {
+        pairs.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_absent_headers_are_none() {
+        let headers = RaxiosHeaders::new();
+        assert_eq!(None, headers.content_length().unwrap());
+        assert_eq!(None, headers.etag().unwrap());
+        assert_eq!(None, headers.cache_control().unwrap());
+    }
+
+    #[test]
+    fn test_scalar_headers() {
+        let headers = headers(&[
+            ("Content-Length", "1024"),
+            ("ETag", "W/\"xyz\""),
+            ("Last-Modified", "Sun, 06 Nov 1994 08:49:37 GMT"),
+            ("Location", "/orders/7"),
+        ]);
+
+        assert_eq!(Some(1024), headers.content_length().unwrap());
+        assert_eq!(
+            Some(EntityTag {
+                weak: true,
+                tag: String::from("xyz"),
+            }),
+            headers.etag().unwrap()
+        );
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(784111777)),
+            headers.last_modified().unwrap()
+        );
+        assert_eq!(Some(String::from("/orders/7")), headers.location().unwrap());
+    }
+
+    #[test]
+    fn test_cache_control_across_values() {
+        let headers = headers(&[
+            ("cache-control", "public, max-age=60"),
+            ("cache-control", "s-maxage=\"120\", Must-Revalidate"),
+        ]);
+        let cache_control = headers.cache_control().unwrap().unwrap();
+
+        assert!(cache_control.is_public());
+        assert!(cache_control.must_revalidate());
+        assert!(!cache_control.no_store());
+        assert_eq!(Some(Duration::from_secs(60)), cache_control.max_age());
+        assert_eq!(Some(Duration::from_secs(120)), cache_control.s_maxage());
+    }
+
+    #[test]
+    fn test_content_disposition_prefers_extended_filename() {
+        let headers = headers(&[(
+            "content-disposition",
+            "Attachment; filename=\"EURO rates.pdf\"; filename*=UTF-8''%e2%82%ac%20rates.pdf",
+        )]);
+
+        assert_eq!(
+            Some(ContentDisposition {
+                disposition: String::from("attachment"),
+                filename: Some(String::from("€ rates.pdf")),
+            }),
+            headers.content_disposition().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cache_control_with_quoted_commas() {
+        let headers = headers(&[("cache-control", "private=\"set-cookie, x-foo\", max-age=60")]);
+        let cache_control = headers.cache_control().unwrap().unwrap();
+
+        assert_eq!(2, cache_control.directives().len());
+        assert_eq!(Some("set-cookie, x-foo"), cache_control.get("private"));
+        assert_eq!(Some(Duration::from_secs(60)), cache_control.max_age());
+    }
+
+    #[test]
+    fn test_repeated_content_length() {
+        assert_eq!(
+            Some(10),
+            headers(&[("content-length", "10, 10")])
+                .content_length()
+                .unwrap()
+        );
+        assert_eq!(
+            Some(10),
+            headers(&[("content-length", "10"), ("content-length", "10")])
+                .content_length()
+                .unwrap()
+        );
+        assert!(headers(&[("content-length", "10, 11")])
+            .content_length()
+            .is_err());
+        assert!(headers(&[("content-length", "10,")])
+            .content_length()
+            .is_err());
+    }
+
+    #[test]
+    fn test_content_disposition_falls_back_to_filename() {
+        let headers = headers(&[(
+            "content-disposition",
+            "attachment; filename*=Shift_JIS''%82%a0.txt; filename=\"fallback.txt\"",
+        )]);
+
+        assert_eq!(
+            Some(ContentDisposition {
+                disposition: String::from("attachment"),
+                filename: Some(String::from("fallback.txt")),
+            }),
+            headers.content_disposition().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_malformed_headers_are_errors() {
+        let headers = headers(&[
+            ("content-length", "-1"),
+            ("etag", "xyz"),
+            ("last-modified", "yesterday"),
+        ]);
+
+        assert!(matches!(
+            headers.content_length(),
+            Err(RaxiosError::HeaderParseError(ref name, ref value))
+                if name == "content-length" && value == "-1"
+        ));
+        assert!(headers.etag().is_err());
+        assert!(headers.last_modified().is_err());
+    }
+
+    #[test]
+    fn test_links_across_values() {
+        let headers = headers(&[
+            (
+                "link",
+                "<https://api.test/users?page=2>; rel=\"next\", <https://api.test/users?page=9>; rel=last",
+            ),
+            ("Link", "</users?page=1>; rel=\"first prev\"; title=\"a, \\\"b\\\"\""),
+        ]);
+        let links = headers.links().unwrap();
+
+        assert_eq!(3, links.len());
+        assert_eq!(
+            Some(String::from("https://api.test/users?page=2")),
+            headers.link("NEXT").unwrap().map(|link| link.target)
+        );
+        assert_eq!(
+            Link {
+                target: String::from("/users?page=1"),
+                params: vec![
+                    (String::from("rel"), String::from("first prev")),
+                    (String::from("title"), String::from("a, \"b\"")),
+                ],
+            },
+            headers.link("prev").unwrap().unwrap()
+        );
+        assert_eq!(None, headers.link("self").unwrap());
+        assert!(RaxiosHeaders::new().links().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_malformed_links_are_errors() {
+        assert!(headers(&[("link", "https://api.test; rel=next")])
+            .links()
+            .is_err());
+        assert!(headers(&[("link", "<https://api.test>; rel=\"next")])
+            .links()
+            .is_err());
+    }
+}
@@ -0,0 +1,538 @@
+use std::{collections::HashMap, fmt::Write, str::FromStr};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{RaxiosError, RaxiosResult, SerializationError};
+
+/// The value of a single URI template variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Variable {
+    String(String),
+    List(Vec<String>),
+    Map(Vec<(String, String)>),
+}
+
+impl Variable {
+    /// Variables which are `null`, or empty lists and maps, are undefined and expand to nothing
+    fn from_value(name: &str, value: Value) -> Result<Option<Self>, SerializationError> {
+        let variable = match value {
+            Value::Null => return Ok(None),
+            Value::Array(items) => Self::List(
+                items
+                    .into_iter()
+                    .map(|item| scalar_to_string(name, item))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            ),
+            Value::Object(fields) => {
+                let mut pairs = Vec::with_capacity(fields.len());
+                for (key, value) in fields {
+                    if let Some(value) = scalar_to_string(name, value)? {
+                        pairs.push((key, value));
+                    }
+                }
+                Self::Map(pairs)
+            }
+            scalar => match scalar_to_string(name, scalar)? {
+                Some(string) => Self::String(string),
+                None => return Ok(None),
+            },
+        };
+
+        Ok(match &variable {
+            Self::List(items) if items.is_empty() => None,
+            Self::Map(pairs) if pairs.is_empty() => None,
+            _ => Some(variable),
+        })
+    }
+}
+
+fn scalar_to_string(name: &str, value: Value) -> Result<Option<String>, SerializationError> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(string) => Ok(Some(string)),
+        Value::Number(_) | Value::Bool(_) => Ok(Some(value.to_string())),
+        Value::Array(_) | Value::Object(_) => Err(SerializationError::Unknown(format!(
+            "URI template variable `{name}` must not contain nested lists or maps"
+        ))),
+    }
+}
+
+/// The variables a `UriTemplate` is expanded with
+///
+/// Strings, numbers and booleans expand as strings, sequences expand as lists and nested
+/// structs and maps expand as associative arrays, in key order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UriVariables {
+    variables: HashMap<String, Variable>,
+}
+
+impl UriVariables {
+    /// Reads the variables from the fields of a struct or map
+    pub fn from_serialize<T>(value: &T) -> Result<Self, SerializationError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let fields = match serde_json::to_value(value).map_err(SerializationError::Json)? {
+            Value::Object(fields) => fields,
+            Value::Null => Default::default(),
+            _ => {
+                return Err(SerializationError::Unknown(String::from(
+                    "URI template variables must serialize to a struct or map",
+                )))
+            }
+        };
+
+        let mut variables = HashMap::with_capacity(fields.len());
+        for (name, value) in fields {
+            if let Some(variable) = Variable::from_value(&name, value)? {
+                variables.insert(name, variable);
+            }
+        }
+        Ok(Self { variables })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Simple,
+    Reserved,
+    Fragment,
+    Label,
+    PathSegment,
+    PathParameter,
+    Query,
+    QueryContinuation,
+}
+
+impl Operator {
+    fn from_char(operator: char) -> Option<Self> {
+        Some(match operator {
+            '+' => Self::Reserved,
+            '#' => Self::Fragment,
+            '.' => Self::Label,
+            '/' => Self::PathSegment,
+            ';' => Self::PathParameter,
+            '?' => Self::Query,
+            '&' => Self::QueryContinuation,
+            _ => return None,
+        })
+    }
+
+    fn first(self) -> &'static str {
+        match self {
+            Self::Simple | Self::Reserved => "",
+            Self::Fragment => "#",
+            Self::Label => ".",
+            Self::PathSegment => "/",
+            Self::PathParameter => ";",
+            Self::Query => "?",
+            Self::QueryContinuation => "&",
+        }
+    }
+
+    fn separator(self) -> &'static str {
+        match self {
+            Self::Simple | Self::Reserved | Self::Fragment => ",",
+            Self::Label => ".",
+            Self::PathSegment => "/",
+            Self::PathParameter => ";",
+            Self::Query | Self::QueryContinuation => "&",
+        }
+    }
+
+    fn named(self) -> bool {
+        matches!(
+            self,
+            Self::PathParameter | Self::Query | Self::QueryContinuation
+        )
+    }
+
+    fn if_empty(self) -> &'static str {
+        match self {
+            Self::Query | Self::QueryContinuation => "=",
+            _ => "",
+        }
+    }
+
+    fn allow_reserved(self) -> bool {
+        matches!(self, Self::Reserved | Self::Fragment)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VarSpec {
+    name: String,
+    prefix: Option<usize>,
+    explode: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Expression {
+        operator: Operator,
+        variables: Vec<VarSpec>,
+    },
+}
+
+fn invalid(template: &str, reason: &str) -> RaxiosError {
+    RaxiosError::InvalidUrl(format!("{template} ({reason})"))
+}
+
+fn parse_expression(template: &str, expression: &str) -> RaxiosResult<Part> {
+    let mut chars = expression.chars();
+    let (operator, variables) = match chars
+        .next()
+        .map(|first| (first, Operator::from_char(first)))
+    {
+        Some((_, Some(operator))) => (operator, chars.as_str()),
+        Some(('=' | ',' | '!' | '@' | '|', None)) => {
+            return Err(invalid(template, "reserved URI template operator"))
+        }
+        _ => (Operator::Simple, expression),
+    };
+
+    let variables = variables
+        .split(',')
+        .map(|spec| {
+            let (name, prefix, explode) = if let Some(name) = spec.strip_suffix('*') {
+                (name, None, true)
+            } else if let Some((name, length)) = spec.split_once(':') {
+                let length = length
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|length| (1..10000).contains(length))
+                    .ok_or_else(|| invalid(template, "invalid URI template prefix length"))?;
+                (name, Some(length), false)
+            } else {
+                (spec, None, false)
+            };
+
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '%'))
+            {
+                return Err(invalid(template, "invalid URI template variable name"));
+            }
+
+            Ok(VarSpec {
+                name: name.to_owned(),
+                prefix,
+                explode,
+            })
+        })
+        .collect::<RaxiosResult<Vec<_>>>()?;
+
+    Ok(Part::Expression {
+        operator,
+        variables,
+    })
+}
+
+/// Percent-encodes everything except unreserved characters, and reserved characters and
+/// existing percent-encoded triplets if `allow_reserved` is set
+fn encode(output: &mut String, value: &str, allow_reserved: bool) {
+    let bytes = value.as_bytes();
+    for (index, c) in value.char_indices() {
+        let allowed = c.is_ascii_alphanumeric()
+            || matches!(c, '-' | '.' | '_' | '~')
+            || (allow_reserved
+                && (":/?#[]@!$&'()*+,;=".contains(c)
+                    || (c == '%'
+                        && bytes.get(index + 1).is_some_and(u8::is_ascii_hexdigit)
+                        && bytes.get(index + 2).is_some_and(u8::is_ascii_hexdigit))));
+        if allowed {
+            output.push(c);
+        } else {
+            let mut buffer = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                let _ = write!(output, "%{byte:02X}");
+            }
+        }
+    }
+}
+
+/// An RFC 6570 URI template, such as `/search{?q,page}` or `/files{/path*}`. All four levels of
+/// the specification are supported.
+///
+/// Pass the variables in `RaxiosOptions::uri_variables` to use a template directly as the
+/// endpoint of a request, or expand it yourself with `UriTemplate::expand`.
+///
+/// # Example
+/// ```rust
+/// use raxios::{UriTemplate, UriVariables};
+///
+/// #[derive(serde::Serialize)]
+/// struct Search {
+///     q: &'static str,
+///     page: Option<u32>,
+///     path: Vec<&'static str>,
+/// }
+///
+/// let template: UriTemplate = "/files{/path*}{?q,page}".parse().unwrap();
+/// let variables = UriVariables::from_serialize(&Search {
+///     q: "hello world",
+///     page: None,
+///     path: vec!["a", "b c"],
+/// })
+/// .unwrap();
+///
+/// assert_eq!("/files/a/b%20c?q=hello%20world", template.expand(&variables));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplate {
+    parts: Vec<Part>,
+}
+
+impl FromStr for UriTemplate {
+    type Err = RaxiosError;
+
+    /// Parses a URI template. Fails with `RaxiosError::InvalidUrl` if an expression is not closed
+    /// or is malformed.
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut remaining = template;
+        while let Some(start) = remaining.find('{') {
+            if start > 0 {
+                parts.push(Part::Literal(remaining[..start].to_owned()));
+            }
+            let end = remaining[start..]
+                .find('}')
+                .ok_or_else(|| invalid(template, "unclosed URI template expression"))?
+                + start;
+            parts.push(parse_expression(template, &remaining[start + 1..end])?);
+            remaining = &remaining[end + 1..];
+        }
+        if !remaining.is_empty() {
+            parts.push(Part::Literal(remaining.to_owned()));
+        }
+
+        Ok(Self { parts })
+    }
+}
+
+impl UriTemplate {
+    /// Expands the template. Variables which are not defined in `variables` expand to nothing.
+    pub fn expand(&self, variables: &UriVariables) -> String {
+        let mut output = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => encode(&mut output, literal, true),
+                Part::Expression {
+                    operator,
+                    variables: specs,
+                } => {
+                    let mut first = true;
+                    for spec in specs {
+                        let Some(variable) = variables.variables.get(&spec.name) else {
+                            continue;
+                        };
+                        output += if first {
+                            operator.first()
+                        } else {
+                            operator.separator()
+                        };
+                        first = false;
+                        expand_variable(&mut output, *operator, spec, variable);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+fn expand_variable(output: &mut String, operator: Operator, spec: &VarSpec, variable: &Variable) {
+    let allow_reserved = operator.allow_reserved();
+    let name_value = |output: &mut String, name: &str, value: &str| {
+        encode(output, name, false);
+        if value.is_empty() {
+            *output += operator.if_empty();
+        } else {
+            output.push('=');
+            encode(output, value, allow_reserved);
+        }
+    };
+
+    match variable {
+        Variable::String(value) => {
+            let value = match spec.prefix {
+                Some(length) => value
+                    .char_indices()
+                    .nth(length)
+                    .map_or(value.as_str(), |(end, _)| &value[..end]),
+                None => value,
+            };
+            if operator.named() {
+                name_value(output, &spec.name, value);
+            } else {
+                encode(output, value, allow_reserved);
+            }
+        }
+        Variable::List(items) if spec.explode => {
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    *output += operator.separator();
+                }
+                if operator.named() {
+                    name_value(output, &spec.name, item);
+                } else {
+                    encode(output, item, allow_reserved);
+                }
+            }
+        }
+        Variable::Map(pairs) if spec.explode => {
+            for (index, (key, value)) in pairs.iter().enumerate() {
+                if index > 0 {
+                    *output += operator.separator();
+                }
+                if operator.named() {
+                    name_value(output, key, value);
+                } else {
+                    encode(output, key, allow_reserved);
+                    output.push('=');
+                    encode(output, value, allow_reserved);
+                }
+            }
+        }
+        Variable::List(_) | Variable::Map(_) => {
+            if operator.named() {
+                encode(output, &spec.name, false);
+                output.push('=');
+            }
+            let values: Vec<&str> = match variable {
+                Variable::List(items) => items.iter().map(String::as_str).collect(),
+                Variable::Map(pairs) => pairs
+                    .iter()
+                    .flat_map(|(key, value)| [key.as_str(), value.as_str()])
+                    .collect(),
+                Variable::String(_) => unreachable!(),
+            };
+            for (index, value) in values.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                encode(output, value, allow_reserved);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{UriTemplate, UriVariables};
+    use crate::RaxiosError;
+
+    /// The example variables from section 3.2 of RFC 6570. Associative arrays expand in key
+    /// order, so `keys` expands as `comma`, `dot`, `semi` rather than in the order of the RFC.
+    fn expand(template: &str) -> String {
+        let variables = UriVariables::from_serialize(&json!({
+            "count": ["one", "two", "three"],
+            "dom": ["example", "com"],
+            "dub": "me/too",
+            "hello": "Hello World!",
+            "half": "50%",
+            "var": "value",
+            "who": "fred",
+            "base": "http://example.com/home/",
+            "path": "/foo/bar",
+            "list": ["red", "green", "blue"],
+            "keys": {"semi": ";", "dot": ".", "comma": ","},
+            "v": "6",
+            "x": 1024,
+            "y": 768,
+            "empty": "",
+            "empty_keys": {},
+            "undef": null,
+        }))
+        .unwrap();
+
+        template.parse::<UriTemplate>().unwrap().expand(&variables)
+    }
+
+    #[test]
+    fn test_simple_string_expansion() {
+        assert_eq!("value", expand("{var}"));
+        assert_eq!("Hello%20World%21", expand("{hello}"));
+        assert_eq!("50%25", expand("{half}"));
+        assert_eq!("OX", expand("O{empty}X"));
+        assert_eq!("OX", expand("O{undef}X"));
+        assert_eq!("1024,768", expand("{x,y}"));
+        assert_eq!("1024,Hello%20World%21,768", expand("{x,hello,y}"));
+        assert_eq!("?1024,", expand("?{x,empty}"));
+        assert_eq!("?1024", expand("?{x,undef}"));
+        assert_eq!("?768", expand("?{undef,y}"));
+        assert_eq!("val", expand("{var:3}"));
+        assert_eq!("value", expand("{var:30}"));
+        assert_eq!("red,green,blue", expand("{list}"));
+        assert_eq!("red,green,blue", expand("{list*}"));
+        assert_eq!("comma,%2C,dot,.,semi,%3B", expand("{keys}"));
+        assert_eq!("comma=%2C,dot=.,semi=%3B", expand("{keys*}"));
+    }
+
+    #[test]
+    fn test_reserved_and_fragment_expansion() {
+        assert_eq!("value", expand("{+var}"));
+        assert_eq!("Hello%20World!", expand("{+hello}"));
+        assert_eq!("50%25", expand("{+half}"));
+        assert_eq!("http%3A%2F%2Fexample.com%2Fhome%2F", expand("{base}"));
+        assert_eq!("http://example.com/home/", expand("{+base}"));
+        assert_eq!("/foo/b/here", expand("{+path:6}/here"));
+        assert_eq!("comma,,,dot,.,semi,;", expand("{+keys}"));
+        assert_eq!("#value", expand("{#var}"));
+        assert_eq!("#Hello%20World!", expand("{#hello}"));
+        assert_eq!("#/foo/b/here", expand("{#path:6}/here"));
+        assert_eq!("#comma=,,dot=.,semi=;", expand("{#keys*}"));
+    }
+
+    #[test]
+    fn test_label_and_path_expansion() {
+        assert_eq!("X.value", expand("X{.var}"));
+        assert_eq!("X.1024.768", expand("X{.x,y}"));
+        assert_eq!("X.red.green.blue", expand("X{.list*}"));
+        assert_eq!("X", expand("X{.empty_keys}"));
+        assert_eq!("www.example.com", expand("www{.dom*}"));
+        assert_eq!("/value", expand("{/var}"));
+        assert_eq!("/value/1024/here", expand("{/var,x}/here"));
+        assert_eq!("/red/green/blue/%2Ffoo", expand("{/list*,path:4}"));
+        assert_eq!("/comma=%2C/dot=./semi=%3B", expand("{/keys*}"));
+        assert_eq!("/v//value", expand("/v{/empty}{/var}"));
+    }
+
+    #[test]
+    fn test_named_expansion() {
+        assert_eq!(";x=1024;y=768", expand("{;x,y}"));
+        assert_eq!(";x=1024;y=768;empty", expand("{;x,y,empty}"));
+        assert_eq!(";list=red;list=green;list=blue", expand("{;list*}"));
+        assert_eq!(";comma=%2C;dot=.;semi=%3B", expand("{;keys*}"));
+        assert_eq!(";hello=Hello", expand("{;hello:5}"));
+        assert_eq!("?x=1024&y=768", expand("{?x,y}"));
+        assert_eq!("?x=1024&y=768&empty=", expand("{?x,y,empty}"));
+        assert_eq!("?list=red,green,blue", expand("{?list}"));
+        assert_eq!("?list=red&list=green&list=blue", expand("{?list*}"));
+        assert_eq!("?keys=comma,%2C,dot,.,semi,%3B", expand("{?keys}"));
+        assert_eq!("?comma=%2C&dot=.&semi=%3B", expand("{?keys*}"));
+        assert_eq!("?fixed=yes&x=1024", expand("?fixed=yes{&x}"));
+        assert_eq!("&var=val", expand("{&var:3}"));
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for template in ["/users/{id", "{=x}", "{var:0}", "{}", "{a b}"] {
+            assert!(
+                matches!(
+                    template.parse::<UriTemplate>(),
+                    Err(RaxiosError::InvalidUrl(_))
+                ),
+                "{template}"
+            );
+        }
+    }
+}
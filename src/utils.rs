@@ -1,6 +1,9 @@
-use std::{collections::HashMap, str::FromStr};
+use std::str::FromStr;
 
-use reqwest::header::{HeaderMap, HeaderName};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Url,
+};
 
 use crate::{
     error::{RaxiosError, RaxiosResult},
@@ -41,38 +44,101 @@ macro_rules! map_string {
     }};
 }
 
-pub fn reqwest_headers_to_map(header: &HeaderMap) -> RaxiosResult<RaxiosHeaders> {
-    let mut to_return: RaxiosHeaders = HashMap::new();
-    for (key, value) in header {
-        to_return.insert(
-            key.to_string(),
-            value
-                .to_str()
-                .map_err(|e| RaxiosError::Unknown(anyhow::anyhow!(e)))?
-                .to_owned(),
-        );
-    }
-
-    return Ok(to_return);
+pub fn reqwest_headers_to_map(header: &HeaderMap) -> RaxiosHeaders {
+    header
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_bytes()))
+        .collect()
 }
 
 pub fn map_to_reqwest_headers(map: &RaxiosHeaders) -> RaxiosResult<HeaderMap> {
     let mut headers = reqwest::header::HeaderMap::new();
-    for (key, value) in map {
-        headers.insert(
-            HeaderName::from_str(key)
-                .map_err(|_| RaxiosError::HeaderParseError(key.to_owned(), value.to_owned()))?,
-            value
-                .parse()
-                .map_err(|_| RaxiosError::HeaderParseError(key.to_owned(), value.to_owned()))?,
+    for (key, value) in map.iter() {
+        let parse_error = || {
+            RaxiosError::HeaderParseError(
+                key.to_owned(),
+                String::from_utf8_lossy(value).into_owned(),
+            )
+        };
+        headers.append(
+            HeaderName::from_str(key).map_err(|_| parse_error())?,
+            HeaderValue::from_bytes(value).map_err(|_| parse_error())?,
         );
     }
     Ok(headers)
 }
 
+/// Whether `endpoint` is an absolute url (`https://host/x`) or a protocol-relative one (`//host/x`)
+fn is_absolute_url(endpoint: &str) -> bool {
+    let scheme_end = endpoint
+        .char_indices()
+        .take_while(|(index, c)| {
+            c.is_ascii_alphabetic() || (*index > 0 && (c.is_ascii_digit() || "+-.".contains(*c)))
+        })
+        .count();
+    let rest = &endpoint[scheme_end..];
+    match scheme_end {
+        0 => rest.starts_with("//"),
+        _ => rest.starts_with("://"),
+    }
+}
+
+/// Collapses runs of `/` in the path of `endpoint`, leaving its query and fragment untouched
+fn collapse_slashes(endpoint: &str) -> String {
+    let path_end = endpoint.find(['?', '#']).unwrap_or(endpoint.len());
+    let (path, rest) = endpoint.split_at(path_end);
+
+    let mut collapsed = String::with_capacity(endpoint.len());
+    for c in path.chars() {
+        if !(c == '/' && collapsed.ends_with('/')) {
+            collapsed.push(c);
+        }
+    }
+    collapsed + rest
+}
+
+/// Resolves `endpoint` against `base_url`, like axios does.
+///
+/// Absolute endpoints bypass the base url entirely, and protocol-relative endpoints take the
+/// scheme of the base url. Otherwise the endpoint is appended to the path of the base url with a
+/// single `/` between them, so `https://host/api/v2` and `users` resolve to
+/// `https://host/api/v2/users`. Repeated slashes in the path of such an endpoint are collapsed, so
+/// `/users//1` becomes `/users/1`. An empty base url only accepts absolute endpoints.
+pub fn resolve_url(base_url: &str, endpoint: &str) -> RaxiosResult<Url> {
+    let invalid = |url: &str| RaxiosError::InvalidUrl(url.to_owned());
+
+    if is_absolute_url(endpoint) {
+        if let Some(protocol_relative) = endpoint.strip_prefix("//") {
+            let base: Url = base_url.parse().map_err(|_| invalid(endpoint))?;
+            let url = format!("{}://{protocol_relative}", base.scheme());
+            return url.parse().map_err(|_| invalid(&url));
+        }
+        return endpoint.parse().map_err(|_| invalid(endpoint));
+    }
+
+    if base_url.is_empty() {
+        return Err(invalid(endpoint));
+    }
+
+    let base = base_url.trim_end_matches('/');
+    let url = if endpoint.is_empty() {
+        base_url.to_owned()
+    } else if endpoint.starts_with(['?', '#']) {
+        format!("{base}{endpoint}")
+    } else {
+        format!(
+            "{base}/{}",
+            collapse_slashes(endpoint.trim_start_matches('/'))
+        )
+    };
+
+    url.parse().map_err(|_| invalid(&url))
+}
+
 #[cfg(test)]
 mod utils_tests {
-    use super::map_to_reqwest_headers;
+    use super::{map_to_reqwest_headers, resolve_url};
+    use crate::RaxiosError;
 
     #[test]
     fn test_map_macro_ident() {
@@ -101,7 +167,79 @@ mod utils_tests {
             value2 : "value",
             value3 : "value"
         };
-        let headers = map_to_reqwest_headers(&header_map);
+        let headers = map_to_reqwest_headers(&header_map.into());
         assert_ne!(true, headers.is_err());
     }
+
+    #[test]
+    fn test_resolve_url_keeps_base_path() {
+        for (base, endpoint) in [
+            ("https://host/api/v2", "users"),
+            ("https://host/api/v2/", "/users"),
+            ("https://host/api/v2//", "/users"),
+        ] {
+            assert_eq!(
+                "https://host/api/v2/users",
+                resolve_url(base, endpoint).unwrap().as_str()
+            );
+        }
+        assert_eq!(
+            "https://host/api?page=2",
+            resolve_url("https://host/api/", "?page=2")
+                .unwrap()
+                .as_str()
+        );
+        assert_eq!(
+            "https://host/api",
+            resolve_url("https://host/api", "").unwrap().as_str()
+        );
+    }
+
+    #[test]
+    fn test_resolve_url_collapses_repeated_slashes() {
+        assert_eq!(
+            "https://host/api/users/1/orders/",
+            resolve_url("https://host/api/", "/users//1///orders//")
+                .unwrap()
+                .as_str()
+        );
+        assert_eq!(
+            "https://host/api/users/1?next=//a#//b",
+            resolve_url("https://host/api", "users//1?next=//a#//b")
+                .unwrap()
+                .as_str()
+        );
+    }
+
+    #[test]
+    fn test_resolve_absolute_endpoints() {
+        assert_eq!(
+            "https://other.host/x",
+            resolve_url("https://host/api", "https://other.host/x")
+                .unwrap()
+                .as_str()
+        );
+        assert_eq!(
+            "https://other.host/x",
+            resolve_url("https://host/api", "//other.host/x")
+                .unwrap()
+                .as_str()
+        );
+        assert_eq!(
+            "http://other.host/x",
+            resolve_url("", "http://other.host/x").unwrap().as_str()
+        );
+    }
+
+    #[test]
+    fn test_resolve_url_with_empty_base() {
+        assert!(matches!(
+            resolve_url("", "/users"),
+            Err(RaxiosError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            resolve_url("", ""),
+            Err(RaxiosError::InvalidUrl(_))
+        ));
+    }
 }